@@ -0,0 +1,117 @@
+use crate::utils;
+use std::fmt;
+
+/// Values available to branch name templates such as `{type}/{issue}-{slug}`.
+#[derive(Debug, Clone, Default)]
+pub struct BranchNameParts {
+    pub commit_type: String,
+    pub slug: String,
+    pub issue: Option<String>,
+}
+
+/// Why `git-cli branch clean` considers a branch safe to delete.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StaleReason {
+    Merged(String),
+    UpstreamGone,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaleBranch {
+    pub name: String,
+    pub reason: StaleReason,
+}
+
+impl fmt::Display for StaleBranch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.reason {
+            StaleReason::Merged(base) => write!(f, "{} (merged into {})", self.name, base),
+            StaleReason::UpstreamGone => write!(f, "{} (upstream gone)", self.name),
+        }
+    }
+}
+
+pub fn is_protected(branch: &str, patterns: &[String]) -> bool {
+    patterns.iter().any(|pattern| utils::glob_match(pattern, branch))
+}
+
+/// Turns free text (usually a commit title) into a branch-safe slug.
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    let slug = slug.trim_end_matches('-');
+    match slug.char_indices().nth(50) {
+        Some((index, _)) => slug[..index].trim_end_matches('-').to_string(),
+        None => slug.to_string(),
+    }
+}
+
+/// Fills in `{type}`, `{slug}` and `{issue}`. Placeholders without a value
+/// are dropped together with the separator that followed them.
+pub fn render_branch_name(template: &str, parts: &BranchNameParts) -> String {
+    let name = template
+        .replace("{type}", &parts.commit_type)
+        .replace("{slug}", &parts.slug)
+        .replace("{issue}", parts.issue.as_deref().unwrap_or_default());
+
+    let mut cleaned = String::new();
+    for c in name.chars() {
+        let previous = cleaned.chars().last();
+        let is_separator = matches!(c, '-' | '_' | '/');
+        match previous {
+            // Skip separators at the start or right after another separator
+            None if is_separator => continue,
+            Some('-' | '_' | '/') if is_separator => {
+                if c == '/' {
+                    cleaned.pop();
+                    cleaned.push('/');
+                }
+                continue;
+            }
+            _ => cleaned.push(c),
+        }
+    }
+
+    cleaned.trim_end_matches(['-', '_', '/']).to_string()
+}
+
+/// Filters `candidates` by `query`, best matches first: exact names, then
+/// prefixes, substrings and finally names containing the query's characters
+/// in order. Matching is case-insensitive.
+pub fn fuzzy_filter<'a>(query: &str, candidates: &'a [String]) -> Vec<&'a String> {
+    let query = query.to_lowercase();
+    let mut matches: Vec<(usize, &String)> = candidates
+        .iter()
+        .filter_map(|candidate| {
+            let name = candidate.to_lowercase();
+            let rank = if name == query {
+                0
+            } else if name.starts_with(&query) {
+                1
+            } else if name.contains(&query) {
+                2
+            } else if is_subsequence(&query, &name) {
+                3
+            } else {
+                return None;
+            };
+            Some((rank, candidate))
+        })
+        .collect();
+
+    // Stable sort keeps the caller's order (e.g. most recent first) within a rank
+    matches.sort_by_key(|(rank, _)| *rank);
+    matches.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut haystack = haystack.chars();
+    needle.chars().all(|c| haystack.any(|h| h == c))
+}
@@ -0,0 +1,300 @@
+use crate::emojis::EmojisObject;
+use crate::errors::Result;
+use crate::message::{CommitMessage, ConventionalHeader};
+use regex::Regex;
+use serde::Serialize;
+
+/// Keep a Changelog sections, in the order they are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum Section {
+    Added,
+    Changed,
+    Deprecated,
+    Removed,
+    Fixed,
+    Security,
+}
+
+impl Section {
+    pub const ALL: [Section; 6] = [
+        Section::Added,
+        Section::Changed,
+        Section::Deprecated,
+        Section::Removed,
+        Section::Fixed,
+        Section::Security,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Section::Added => "Added",
+            Section::Changed => "Changed",
+            Section::Deprecated => "Deprecated",
+            Section::Removed => "Removed",
+            Section::Fixed => "Fixed",
+            Section::Security => "Security",
+        }
+    }
+
+    pub fn heading(&self) -> String {
+        let emoji = match self {
+            Section::Added => "✨",
+            Section::Changed => "♻️",
+            Section::Deprecated => "🗑️",
+            Section::Removed => "🔥",
+            Section::Fixed => "🐛",
+            Section::Security => "🔒",
+        };
+        format!("### {} {}", emoji, self.name())
+    }
+
+    /// The section a commit belongs in, or `None` for housekeeping (docs,
+    /// tests, CI, tooling) that does not belong in a changelog.
+    fn for_commit(emoji: Option<&str>, commit_type: Option<&str>, breaking: bool) -> Option<Self> {
+        let by_emoji = emoji.and_then(|emoji| match emoji.trim_end_matches('\u{fe0f}') {
+            "✨" | "🎉" | "➕" | "🌐" | "🚩" => Some(Some(Section::Added)),
+            "🐛" | "🚑" | "🩹" | "🥅" | "✏" => Some(Some(Section::Fixed)),
+            "🔒" | "🔐" | "🛂" => Some(Some(Section::Security)),
+            "🔥" | "➖" | "⚰" => Some(Some(Section::Removed)),
+            "🗑" => Some(Some(Section::Deprecated)),
+            "♻" | "⚡" | "💄" | "🚚" | "⬆" | "⬇" | "📌" | "💥" | "⏪" | "🏗" | "🗃" | "🔊" | "🔇" => {
+                Some(Some(Section::Changed))
+            }
+            "📝" | "✅" | "🧪" | "👷" | "💚" | "🔧" | "🔨" | "🎨" | "🚨" | "🔖" | "🙈" | "🔀" | "💡" => Some(None),
+            _ => None,
+        });
+
+        let by_type = commit_type.and_then(|commit_type| match commit_type {
+            "feat" => Some(Some(Section::Added)),
+            "fix" => Some(Some(Section::Fixed)),
+            "security" => Some(Some(Section::Security)),
+            "perf" | "refactor" | "revert" | "build" => Some(Some(Section::Changed)),
+            "docs" | "test" | "ci" | "chore" | "style" => Some(None),
+            _ => None,
+        });
+
+        // Breaking changes are always worth listing, even in housekeeping commits
+        match by_emoji.or(by_type).unwrap_or(Some(Section::Changed)) {
+            None if breaking => Some(Section::Changed),
+            section => section,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChangelogEntry {
+    pub hash: String,
+    pub section: Section,
+    pub scope: Option<String>,
+    pub description: String,
+    pub breaking: bool,
+    pub issues: Vec<String>,
+}
+
+impl ChangelogEntry {
+    pub fn from_commit(hash: &str, raw_message: &str, emojis_object: &EmojisObject) -> Option<Self> {
+        let message = CommitMessage::parse(raw_message, emojis_object);
+        let header = ConventionalHeader::parse(&message.title);
+        let breaking = message.is_breaking();
+        let section = Section::for_commit(message.emoji.as_deref(), header.as_ref().map(|h| h.commit_type), breaking)?;
+
+        let issue_regex = Regex::new(r"#(\d+)").ok()?;
+        let mut issues: Vec<String> = Vec::new();
+        let text = format!("{}\n{}", message.title, message.body.as_deref().unwrap_or_default());
+        for captures in issue_regex.captures_iter(&text) {
+            let issue = captures[1].to_string();
+            if !issues.contains(&issue) {
+                issues.push(issue);
+            }
+        }
+
+        // Drop "(#12)" suffixes added by squash merges; the issues are listed separately
+        let description = header.as_ref().map_or(message.title.as_str(), |h| h.description);
+        let description = Regex::new(r"\s*\(#\d+\)").ok()?.replace_all(description, "").trim().to_string();
+
+        Some(Self {
+            hash: hash[..hash.len().min(7)].to_string(),
+            section,
+            scope: header.and_then(|h| h.scope).map(str::to_string),
+            description: capitalize(&description),
+            breaking,
+            issues,
+        })
+    }
+
+    /// A Markdown list item; issues are linked when `issue_url` (with an
+    /// `{id}` placeholder) is known.
+    pub fn render(&self, issue_url: Option<&str>) -> String {
+        let mut line = String::from("- ");
+        if self.breaking {
+            line.push_str("**BREAKING:** ");
+        }
+        if let Some(scope) = &self.scope {
+            line.push_str(&format!("**{}**: ", scope));
+        }
+        line.push_str(&self.description);
+
+        if !self.issues.is_empty() {
+            let issues: Vec<String> = self
+                .issues
+                .iter()
+                .map(|id| match issue_url {
+                    Some(url) => format!("[#{}]({})", id, url.replace("{id}", id)),
+                    None => format!("#{}", id),
+                })
+                .collect();
+            line.push_str(&format!(" ({})", issues.join(", ")));
+        }
+        line.push_str(&format!(" ({})", self.hash));
+        line
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// The `### Section` blocks for `entries`, without a version heading.
+pub fn render_sections(entries: &[ChangelogEntry], issue_url: Option<&str>) -> String {
+    let mut blocks = Vec::new();
+    for section in Section::ALL {
+        let lines: Vec<String> = entries
+            .iter()
+            .filter(|e| e.section == section)
+            .map(|e| e.render(issue_url))
+            .collect();
+        if !lines.is_empty() {
+            blocks.push(format!("{}\n{}\n", section.heading(), lines.join("\n")));
+        }
+    }
+    blocks.join("\n")
+}
+
+pub fn render_markdown(title: &str, entries: &[ChangelogEntry], issue_url: Option<&str>) -> String {
+    format!("## {}\n\n{}", title, render_sections(entries, issue_url))
+}
+
+#[derive(Serialize)]
+struct JsonChangelog<'a> {
+    from: Option<&'a str>,
+    to: &'a str,
+    entries: &'a [ChangelogEntry],
+}
+
+pub fn render_json(from: Option<&str>, to: &str, entries: &[ChangelogEntry]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(&JsonChangelog { from, to, entries })?)
+}
+
+/// Merges `entries` into the `## [Unreleased]` section of an existing
+/// changelog. Hand-written lines are kept, entries already listed (by commit
+/// hash) are skipped, and the section is created above the first release if
+/// it does not exist yet.
+pub fn update_unreleased(existing: &str, entries: &[ChangelogEntry], issue_url: Option<&str>) -> String {
+    let lines: Vec<&str> = existing.lines().collect();
+    let is_release_heading = |line: &str| line.starts_with("## ");
+    let start = lines.iter().position(|l| l.trim_start_matches("## ").trim().eq_ignore_ascii_case("[unreleased]") && is_release_heading(l));
+
+    let (before, section, after): (Vec<&str>, Vec<&str>, Vec<&str>) = match start {
+        Some(start) => {
+            let end = lines[start + 1..].iter().position(|l| is_release_heading(l)).map_or(lines.len(), |i| start + 1 + i);
+            (lines[..start].to_vec(), lines[start + 1..end].to_vec(), lines[end..].to_vec())
+        }
+        None => {
+            let first_release = lines.iter().position(|l| is_release_heading(l)).unwrap_or(lines.len());
+            (lines[..first_release].to_vec(), Vec::new(), lines[first_release..].to_vec())
+        }
+    };
+
+    // Existing subsections keyed by their name without the emoji
+    let mut subsections: Vec<(String, Vec<String>)> = Vec::new();
+    let mut preamble: Vec<String> = Vec::new();
+    for line in section {
+        if let Some(heading) = line.strip_prefix("### ") {
+            subsections.push((heading.to_string(), Vec::new()));
+        } else if let Some((_, body)) = subsections.last_mut() {
+            body.push(line.to_string());
+        } else {
+            preamble.push(line.to_string());
+        }
+    }
+
+    let heading_name = |heading: &str| heading.split_whitespace().last().unwrap_or_default().to_string();
+    let rank = |heading: &str| Section::ALL.iter().position(|s| s.name() == heading_name(heading)).unwrap_or(usize::MAX);
+    for section in Section::ALL {
+        let new_lines: Vec<String> = entries
+            .iter()
+            .filter(|e| e.section == section && !existing.contains(&format!("({})", e.hash)))
+            .map(|e| e.render(issue_url))
+            .collect();
+        if new_lines.is_empty() {
+            continue;
+        }
+
+        match subsections.iter_mut().find(|(heading, _)| heading_name(heading) == section.name()) {
+            Some((_, body)) => {
+                let last_item = body.iter().rposition(|l| !l.trim().is_empty()).map_or(0, |i| i + 1);
+                body.splice(last_item..last_item, new_lines);
+            }
+            None => {
+                let position = subsections
+                    .iter()
+                    .position(|(heading, _)| rank(heading) > section as usize)
+                    .unwrap_or(subsections.len());
+                let mut body = new_lines;
+                body.push(String::new());
+                subsections.insert(position, (section.heading().trim_start_matches("### ").to_string(), body));
+            }
+        }
+    }
+
+    let mut output: Vec<String> = before.iter().map(|l| l.to_string()).collect();
+    if output.last().is_some_and(|l| !l.trim().is_empty()) {
+        output.push(String::new());
+    }
+    output.push("## [Unreleased]".to_string());
+    if preamble.iter().all(|l| l.trim().is_empty()) {
+        output.push(String::new());
+    } else {
+        output.extend(preamble);
+    }
+    for (heading, body) in subsections {
+        output.push(format!("### {}", heading));
+        output.extend(body);
+        if output.last().is_some_and(|l| !l.trim().is_empty()) {
+            output.push(String::new());
+        }
+    }
+    output.extend(after.iter().map(|l| l.to_string()));
+
+    let mut result = output.join("\n");
+    if existing.ends_with('\n') || existing.is_empty() {
+        result.push('\n');
+    }
+    result
+}
+
+/// Issue link template (with an `{id}` placeholder) for a remote URL such as
+/// `git@github.com:owner/repo.git`; GitLab hosts use their `/-/issues/` path.
+pub fn issue_url_for_remote(remote_url: &str) -> Option<String> {
+    let url = remote_url.trim().trim_end_matches('/').trim_end_matches(".git");
+    let (host, path) = if let Some(rest) = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://")) {
+        rest.split_once('/')?
+    } else if let Some(rest) = url.strip_prefix("ssh://") {
+        let (host, path) = rest.split_once('/')?;
+        (host.split_once(':').map_or(host, |(host, _)| host), path)
+    } else {
+        url.split_once(':')?
+    };
+
+    let host = host.rsplit('@').next()?;
+    if host.is_empty() || path.split('/').filter(|p| !p.is_empty()).count() < 2 {
+        return None;
+    }
+
+    let issues = if host.contains("gitlab") { "-/issues" } else { "issues" };
+    Some(format!("https://{}/{}/{}/{{id}}", host, path, issues))
+}
@@ -7,6 +7,22 @@ pub struct Config {
     pub general: GeneralConfig,
     pub commit: CommitConfig,
     pub hooks: HooksConfig,
+    #[serde(default)]
+    pub sync: SyncConfig,
+    #[serde(default)]
+    pub push: PushConfig,
+    #[serde(default)]
+    pub branches: BranchesConfig,
+    #[serde(default)]
+    pub release: ReleaseConfig,
+    #[serde(default)]
+    pub changelog: ChangelogConfig,
+    #[serde(default)]
+    pub signing: SigningConfig,
+    #[serde(default)]
+    pub fixers: Vec<Fixer>,
+    #[serde(default)]
+    pub scan: ScanConfig,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -31,6 +47,208 @@ pub struct HooksConfig {
     pub retry_on_failure: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct SyncConfig {
+    pub pull_strategy: PullStrategy,
+    pub autostash: bool,
+    pub pull_before_push: bool,
+    pub mirror_remotes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct PushConfig {
+    pub follow_tags: bool,
+    pub push_options: Vec<String>,
+    pub force_with_lease_after_rewrite: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct BranchesConfig {
+    pub protected: Vec<String>,
+    pub name_template: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ReleaseConfig {
+    pub tag_prefix: String,
+    pub sign_tags: bool,
+    pub version_files: Vec<VersionFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ChangelogConfig {
+    pub path: String,
+    /// Link template for issue references, e.g. `https://tracker/issues/{id}`.
+    /// Derived from the upstream remote when unset.
+    pub issue_url: Option<String>,
+}
+
+/// A file whose version is bumped on release. Cargo.toml, package.json and
+/// pyproject.toml are recognized by name; anything else needs a `pattern`
+/// whose `version` (or first) capture group is the version.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VersionFile {
+    pub path: String,
+    #[serde(default)]
+    pub pattern: Option<String>,
+}
+
+/// A formatter run on the staged files matching `glob` when
+/// `hooks.auto_fix_lint` is on. `{files}` in `command` is replaced by those
+/// files; without the placeholder they are appended.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Fixer {
+    pub glob: String,
+    pub command: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ScanConfig {
+    /// Scan the staged files for secrets and large files before committing.
+    pub enabled: bool,
+    /// Staged files above this size block the commit unless moved to Git LFS.
+    pub max_file_size_kb: u64,
+    /// Bits per character from which long random-looking tokens are
+    /// reported; 0 turns the check off.
+    pub entropy_threshold: f64,
+    /// Allowed paths and values, relative to the repository root.
+    pub allowlist_file: String,
+    /// Extra detectors on top of the built-in ones.
+    pub patterns: Vec<ScanPattern>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScanPattern {
+    pub name: String,
+    pub regex: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct SigningConfig {
+    pub mode: SigningMode,
+    /// Overrides `gpg.format` for git-cli's commits and tags.
+    pub format: Option<SigningFormat>,
+    /// Overrides `user.signingkey`.
+    pub key: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SigningMode {
+    Always,
+    Never,
+    /// Sign when git's own `commit.gpgsign` says so.
+    #[default]
+    Inherit,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SigningFormat {
+    Gpg,
+    Ssh,
+    X509,
+}
+
+impl SigningFormat {
+    /// The value git expects in `gpg.format`.
+    pub fn git_format(&self) -> &'static str {
+        match self {
+            SigningFormat::Gpg => "openpgp",
+            SigningFormat::Ssh => "ssh",
+            SigningFormat::X509 => "x509",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PullStrategy {
+    Rebase,
+    Merge,
+    FfOnly,
+    Never,
+}
+
+impl std::fmt::Display for PullStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PullStrategy::Rebase => write!(f, "rebase"),
+            PullStrategy::Merge => write!(f, "merge"),
+            PullStrategy::FfOnly => write!(f, "ff-only"),
+            PullStrategy::Never => write!(f, "never"),
+        }
+    }
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            pull_strategy: PullStrategy::Rebase,
+            autostash: true,
+            pull_before_push: true,
+            mirror_remotes: Vec::new(),
+        }
+    }
+}
+
+impl Default for PushConfig {
+    fn default() -> Self {
+        Self {
+            follow_tags: false,
+            push_options: Vec::new(),
+            force_with_lease_after_rewrite: true,
+        }
+    }
+}
+
+impl Default for BranchesConfig {
+    fn default() -> Self {
+        Self {
+            protected: Vec::new(),
+            name_template: "{type}/{slug}".to_string(),
+        }
+    }
+}
+
+impl Default for ReleaseConfig {
+    fn default() -> Self {
+        Self {
+            tag_prefix: "v".to_string(),
+            sign_tags: false,
+            version_files: Vec::new(),
+        }
+    }
+}
+
+impl Default for ChangelogConfig {
+    fn default() -> Self {
+        Self {
+            path: "CHANGELOG.md".to_string(),
+            issue_url: None,
+        }
+    }
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_file_size_kb: 10 * 1024,
+            entropy_threshold: 4.5,
+            allowlist_file: ".git-cli-allowlist".to_string(),
+            patterns: Vec::new(),
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -50,6 +268,14 @@ impl Default for Config {
                 auto_fix_lint: true,
                 retry_on_failure: true,
             },
+            sync: SyncConfig::default(),
+            push: PushConfig::default(),
+            branches: BranchesConfig::default(),
+            release: ReleaseConfig::default(),
+            changelog: ChangelogConfig::default(),
+            signing: SigningConfig::default(),
+            fixers: Vec::new(),
+            scan: ScanConfig::default(),
         }
     }
 }
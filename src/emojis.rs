@@ -18,18 +18,18 @@ pub struct EmojisObject {
 }
 
 pub fn get_emojis() -> Result<EmojisObject, serde_json::Error> {
-    let home_dir = dirs::home_dir().expect(&utils::format_error_message(
-        "Failed to get home directory.",
-    ));
+    let home_dir = dirs::home_dir().unwrap_or_else(|| {
+        panic!("{}", utils::format_error_message("Failed to get home directory."))
+    });
     let emojis_json_path = home_dir.join("emojis.json");
 
     if emojis_json_path.exists() {
-        let emojis_json = std::fs::read_to_string(emojis_json_path).expect(
-            &utils::format_error_message("Failed to read file from home directory."),
-        );
+        let emojis_json = std::fs::read_to_string(emojis_json_path).unwrap_or_else(|_| {
+            panic!("{}", utils::format_error_message("Failed to read file from home directory."))
+        });
 
         let emojis_object: EmojisObject = serde_json::from_str(&emojis_json)
-            .expect(&utils::format_error_message("Invalid JSON format."));
+            .unwrap_or_else(|_| panic!("{}", utils::format_error_message("Invalid JSON format.")));
 
         return Ok(emojis_object);
     }
@@ -559,12 +559,17 @@ pub fn get_emojis() -> Result<EmojisObject, serde_json::Error> {
     "#;
 
     let emojis_object: EmojisObject = serde_json::from_str(emoji_data)
-        .expect(&utils::format_error_message("Invalid JSON format."));
+        .unwrap_or_else(|_| panic!("{}", utils::format_error_message("Invalid JSON format.")));
 
-    std::fs::write(&emojis_json_path, emoji_data).expect(&utils::format_error_message(&format!(
-        "Failed to write file to home directory: {}",
-        emojis_json_path.display()
-    )));
+    std::fs::write(&emojis_json_path, emoji_data).unwrap_or_else(|_| {
+        panic!(
+            "{}",
+            utils::format_error_message(&format!(
+                "Failed to write file to home directory: {}",
+                emojis_json_path.display()
+            ))
+        )
+    });
 
-    return Ok(emojis_object);
+    Ok(emojis_object)
 }
@@ -31,6 +31,21 @@ pub enum GitCliError {
     
     #[error("Validation error: {0}")]
     ValidationError(String),
+
+    #[error("Commit {0} has already been pushed to the upstream")]
+    AlreadyPushed(String),
+
+    #[error("Cannot undo: {0}")]
+    UndoUnavailable(String),
+
+    #[error("Branch {0} is protected")]
+    ProtectedBranch(String),
+
+    #[error("Commit blocked by {0} scanner findings")]
+    ScanFindings(usize),
+
+    #[error("Fixer `{command}` failed:\n{output}")]
+    FixerFailed { command: String, output: String },
 }
 
 pub type Result<T> = std::result::Result<T, GitCliError>;
@@ -1,4 +1,9 @@
+use crate::config::{PullStrategy, SigningConfig, SigningMode};
 use crate::errors::{Result, GitCliError};
+use crate::hooks::{self, FileSnapshot, PreCommitHook};
+use crate::stash::StashEntry;
+use std::collections::BTreeSet;
+use std::path::{Component, PathBuf};
 use std::process::{Command, Stdio};
 use std::io::{BufRead, BufReader};
 use log::{debug, info, warn};
@@ -10,17 +15,436 @@ pub struct Change {
     pub value: String,
 }
 
+impl Change {
+    pub fn is_submodule(&self) -> bool {
+        self.change_type == "Submodule"
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FixupKind {
+    Fixup,
+    Squash,
+}
+
+impl FixupKind {
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            FixupKind::Fixup => "fixup!",
+            FixupKind::Squash => "squash!",
+        }
+    }
+}
+
+/// An operation stopped half-way with conflicts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictState {
+    None,
+    Rebase,
+    Merge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictSide {
+    Ours,
+    Theirs,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Upstream {
+    pub remote: String,
+    pub branch: String,
+}
+
+impl std::fmt::Display for Upstream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.remote, self.branch)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Divergence {
+    pub ahead: usize,
+    pub behind: usize,
+}
+
+impl Divergence {
+    /// Parses `git rev-list --left-right --count HEAD...@{u}` output ("2\t1").
+    pub fn parse(output: &str) -> Option<Self> {
+        let mut counts = output.split_whitespace().map(|n| n.parse::<usize>());
+        match (counts.next(), counts.next()) {
+            (Some(Ok(ahead)), Some(Ok(behind))) => Some(Self { ahead, behind }),
+            _ => None,
+        }
+    }
+
+    pub fn is_diverged(&self) -> bool {
+        self.ahead > 0 && self.behind > 0
+    }
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ahead == 0 && self.behind == 0 {
+            write!(f, "up to date")
+        } else {
+            write!(f, "ahead {}, behind {}", self.ahead, self.behind)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ForceMode {
+    #[default]
+    None,
+    /// `--force-with-lease`, expecting the remote branch at `expected`.
+    WithLease { expected: Option<String> },
+    Force,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PushOptions {
+    pub set_upstream: bool,
+    pub force: ForceMode,
+    pub follow_tags: bool,
+    pub push_options: Vec<String>,
+    pub dry_run: bool,
+}
+
+impl PushOptions {
+    /// The `git push` argv for pushing `branch` to `remote_branch` on `remote`.
+    /// `tracked` is the remote-tracking hash a lease without an explicit
+    /// expected hash is pinned to; None means the branch must not exist yet.
+    pub fn to_args(&self, remote: &str, branch: &str, remote_branch: &str, tracked: Option<&str>) -> Vec<String> {
+        let mut args: Vec<String> = vec!["push".to_string()];
+
+        if self.set_upstream {
+            args.push("--set-upstream".to_string());
+        }
+        match &self.force {
+            ForceMode::None => {}
+            ForceMode::WithLease { expected } => {
+                let expected = expected.as_deref().or(tracked).unwrap_or_default();
+                args.push(format!("--force-with-lease={}:{}", remote_branch, expected));
+            }
+            ForceMode::Force => args.push("--force".to_string()),
+        }
+        if self.follow_tags {
+            args.push("--follow-tags".to_string());
+        }
+        for push_option in &self.push_options {
+            args.push(format!("--push-option={}", push_option));
+        }
+        if self.dry_run {
+            args.push("--dry-run".to_string());
+        }
+        args.push(remote.to_string());
+        args.push(format!("{}:{}", branch, remote_branch));
+        args
+    }
+}
+
+/// Result of verifying a commit signature, from git's `%G?` placeholder.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SignatureStatus {
+    Good,
+    /// Good signature whose key validity is unknown or expired.
+    GoodUntrusted,
+    Bad,
+    /// Signed, but the signature cannot be checked (e.g. missing key).
+    Unverifiable,
+    Unsigned,
+}
+
+impl SignatureStatus {
+    pub fn parse(code: &str) -> Self {
+        match code.trim() {
+            "G" => SignatureStatus::Good,
+            "U" | "X" | "Y" => SignatureStatus::GoodUntrusted,
+            "B" | "R" => SignatureStatus::Bad,
+            "E" => SignatureStatus::Unverifiable,
+            _ => SignatureStatus::Unsigned,
+        }
+    }
+
+    pub fn is_signed(&self) -> bool {
+        !matches!(self, SignatureStatus::Unsigned | SignatureStatus::Bad)
+    }
+}
+
+impl std::fmt::Display for SignatureStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignatureStatus::Good => write!(f, "good signature"),
+            SignatureStatus::GoodUntrusted => write!(f, "good signature from an untrusted or expired key"),
+            SignatureStatus::Bad => write!(f, "BAD signature"),
+            SignatureStatus::Unverifiable => write!(f, "signed, but the signature cannot be checked"),
+            SignatureStatus::Unsigned => write!(f, "not signed"),
+        }
+    }
+}
+
+/// Signature details of a single commit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitSignature {
+    pub hash: String,
+    pub subject: String,
+    pub status: SignatureStatus,
+    pub signer: Option<String>,
+}
+
+/// A submodule whose checked-out commit or working tree differs from what
+/// the parent repository records.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Submodule {
+    /// Path relative to the work tree root.
+    pub path: String,
+    /// Commit recorded in the parent's HEAD, None for a newly added submodule.
+    pub recorded: Option<String>,
+    /// Commit checked out in the submodule.
+    pub head: Option<String>,
+    /// `recorded..head` as "hash subject" lines, newest first.
+    pub new_commits: Vec<String>,
+    /// The checked-out (or staged) commit is not the recorded one.
+    pub commit_changed: bool,
+    pub modified: bool,
+    pub untracked: bool,
+}
+
+impl Submodule {
+    /// Parses a `git status --porcelain=v2` entry, returning None for entries
+    /// that are not submodules.
+    pub fn parse_status(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(9, ' ');
+        if fields.next()? != "1" {
+            return None;
+        }
+        let _xy = fields.next()?;
+        let state = fields.next()?.strip_prefix('S')?.as_bytes();
+        let (_head_mode, _index_mode, _worktree_mode) = (fields.next()?, fields.next()?, fields.next()?);
+        let (head_hash, index_hash) = (fields.next()?, fields.next()?);
+        let path = fields.next()?.to_string();
+
+        let recorded = Some(head_hash.to_string()).filter(|hash| hash.bytes().any(|b| b != b'0'));
+        Some(Self {
+            path,
+            commit_changed: state.first() == Some(&b'C') || recorded.as_deref() != Some(index_hash),
+            recorded,
+            head: None,
+            new_commits: Vec::new(),
+            modified: state.get(1) == Some(&b'M'),
+            untracked: state.get(2) == Some(&b'U'),
+        })
+    }
+
+    /// Uncommitted changes inside the submodule itself.
+    pub fn is_dirty(&self) -> bool {
+        self.modified || self.untracked
+    }
+
+    /// Title and body for the parent commit that records the new pointer.
+    pub fn update_message(&self) -> (String, Option<String>) {
+        let short = |hash: &str| hash[..hash.len().min(7)].to_string();
+        let target = self.head.as_deref().map(short).unwrap_or_default();
+
+        match (&self.recorded, self.new_commits.len()) {
+            (None, _) => (format!("Add {} submodule at {}", self.path, target), None),
+            (Some(_), 0) => (format!("Point {} submodule to {}", self.path, target), None),
+            (Some(recorded), count) => {
+                let title = format!("Update {} submodule to {}", self.path, target);
+                let noun = if count == 1 { "commit" } else { "commits" };
+                let mut body = format!("Includes {} {} since {}:\n", count, noun, short(recorded));
+                for commit in &self.new_commits {
+                    body.push_str(&format!("\n- {}", commit));
+                }
+                (title, Some(body))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Submodule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut states = Vec::new();
+        match self.new_commits.len() {
+            0 if self.commit_changed => states.push("pointer changed".to_string()),
+            0 => {}
+            1 => states.push("1 new commit".to_string()),
+            count => states.push(format!("{} new commits", count)),
+        }
+        if self.modified {
+            states.push("modified content".to_string());
+        }
+        if self.untracked {
+            states.push("untracked files".to_string());
+        }
+        write!(f, "{}: {}", self.path, states.join(", "))
+    }
+}
+
+/// An entry of `git diff --cached --name-status`: what the next commit contains.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StagedFile {
+    /// Status letter, e.g. "M", or "R" for a rename.
+    pub status: String,
+    pub path: String,
+    /// The source of a rename or copy.
+    pub old_path: Option<String>,
+}
+
+impl StagedFile {
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        let status: String = fields.next()?.chars().take(1).collect();
+        let first = fields.next()?.to_string();
+        match fields.next() {
+            Some(second) => Some(Self { status, path: second.to_string(), old_path: Some(first) }),
+            None => Some(Self { status, path: first, old_path: None }),
+        }
+    }
+
+    /// Whether the file is part of a selection of status entries, which may
+    /// name directories (`src/`) or renames (`old -> new`).
+    pub fn is_selected(&self, selection: &[String]) -> bool {
+        let covers = |path: &str| {
+            selection
+                .iter()
+                .flat_map(|entry| entry.split(" -> "))
+                .any(|entry| entry == path || (entry.ends_with('/') && path.starts_with(entry)))
+        };
+        covers(&self.path) || self.old_path.as_deref().is_some_and(covers)
+    }
+
+    /// The paths whose index entries make up this change.
+    pub fn paths(&self) -> Vec<String> {
+        let mut paths = vec![self.path.clone()];
+        paths.extend(self.old_path.clone());
+        paths
+    }
+}
+
+impl std::fmt::Display for StagedFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.old_path {
+            Some(old_path) => write!(f, "{}  {} -> {}", self.status, old_path, self.path),
+            None => write!(f, "{}  {}", self.status, self.path),
+        }
+    }
+}
+
 pub struct GitService {
     pub debug: bool,
+    signing: SigningConfig,
+    work_tree: Option<PathBuf>,
+    verify: bool,
 }
 
 impl GitService {
     pub fn new(debug: bool) -> Self {
-        Self { debug }
+        Self { debug, signing: SigningConfig::default(), work_tree: None, verify: true }
+    }
+
+    /// With `verify` off, commits pass `--no-verify` and skip the git hooks.
+    pub fn with_verify(mut self, verify: bool) -> Self {
+        self.verify = verify;
+        self
+    }
+
+    /// Runs every git command from the work tree root, so the root-relative
+    /// paths git reports can be passed back to it unchanged.
+    pub fn with_work_tree(mut self, root: PathBuf) -> Self {
+        self.work_tree = Some(root);
+        self
+    }
+
+    fn git(&self) -> Command {
+        let mut command = Command::new("git");
+        if let Some(root) = &self.work_tree {
+            command.current_dir(root);
+        }
+        command
+    }
+
+    /// A path printed by git, resolved against the directory git runs in.
+    fn resolve_path(&self, path: &str) -> PathBuf {
+        match &self.work_tree {
+            Some(root) => root.join(path),
+            None => PathBuf::from(path),
+        }
+    }
+
+    /// Converts a path given relative to the current directory (e.g. on the
+    /// command line) into one relative to the work tree root.
+    pub fn repo_path(&self, path: &str) -> Result<String> {
+        let absolute = std::env::current_dir()?.join(path);
+        let mut normalized = PathBuf::new();
+        for component in absolute.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    normalized.pop();
+                }
+                component => normalized.push(component),
+            }
+        }
+
+        let root = self.get_work_tree_root()?;
+        let relative = normalized
+            .strip_prefix(&root)
+            .map_err(|_| GitCliError::ValidationError(format!("{} is outside the repository", path)))?;
+        Ok(relative.to_string_lossy().to_string())
+    }
+
+    /// Applies the `[signing]` policy to commits, rebases and tags.
+    pub fn with_signing(mut self, signing: SigningConfig) -> Self {
+        self.signing = signing;
+        self
+    }
+
+    /// `-c` overrides for the configured signing format and key, placed
+    /// before the git subcommand.
+    fn signing_config_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(format) = self.signing.format {
+            args.push("-c".to_string());
+            args.push(format!("gpg.format={}", format.git_format()));
+        }
+        if let Some(key) = &self.signing.key {
+            args.push("-c".to_string());
+            args.push(format!("user.signingkey={}", key));
+        }
+        args
+    }
+
+    /// `--gpg-sign`/`--no-gpg-sign` for commands that create commits.
+    fn signing_flag(&self) -> Option<String> {
+        match self.signing.mode {
+            SigningMode::Always => Some("--gpg-sign".to_string()),
+            SigningMode::Never => Some("--no-gpg-sign".to_string()),
+            SigningMode::Inherit => None,
+        }
+    }
+
+    /// Whether the policy (or git's `commit.gpgsign` when inheriting) expects
+    /// commits to be signed.
+    pub fn signing_required(&self) -> Result<bool> {
+        Ok(match self.signing.mode {
+            SigningMode::Always => true,
+            SigningMode::Never => false,
+            SigningMode::Inherit => self.get_config_bool("commit.gpgsign")?,
+        })
+    }
+
+    /// `subcommand` prefixed with the signing overrides and followed by the signing flag.
+    fn signing_args(&self, subcommand: &str) -> Vec<String> {
+        let mut args = self.signing_config_args();
+        args.push(subcommand.to_string());
+        args.extend(self.signing_flag());
+        args
     }
 
     pub fn verify_git_initialized(&self) -> Result<()> {
-        let output = Command::new("git")
+        let output = self.git()
             .arg("rev-parse")
             .arg("--is-inside-work-tree")
             .output()
@@ -35,7 +459,7 @@ impl GitService {
     }
 
     pub fn get_status(&self) -> Result<Vec<Change>> {
-        let output = Command::new("git")
+        let output = self.git()
             .arg("status")
             .arg("--porcelain")
             .output()
@@ -103,16 +527,27 @@ impl GitService {
             changes.push(change);
         }
 
+        // Submodule entries are pointer bumps or dirty submodules, not file edits
+        let submodules = self.get_changed_submodules()?;
+        for change in changes.iter_mut().filter(|c| submodules.iter().any(|s| s.path == c.value)) {
+            change.color = "\x1b[1;36m".to_string(); // Bold cyan for submodules
+            change.change_type = "Submodule".to_string();
+        }
+
         debug!("Found {} changes", changes.len());
         Ok(changes)
     }
 
     pub fn add_files(&self, files: &[String]) -> Result<()> {
-        let mut cmd = Command::new("git");
+        let mut cmd = self.git();
         cmd.arg("add");
         
         if files.is_empty() {
             cmd.arg(".");
+            // Submodule pointers are committed on their own once reviewed
+            for submodule in self.get_changed_submodules()? {
+                cmd.arg(format!(":(top,exclude){}", submodule.path));
+            }
         } else {
             cmd.args(files);
         }
@@ -130,8 +565,18 @@ impl GitService {
     }
 
     pub fn run_command_stream(&self, args: Vec<&str>, error_message: &str) -> Result<(String, bool)> {
-        let mut child = Command::new("git")
+        self.run_command_stream_with_env(args, &[], error_message)
+    }
+
+    pub fn run_command_stream_with_env(
+        &self,
+        args: Vec<&str>,
+        envs: &[(&str, &str)],
+        error_message: &str,
+    ) -> Result<(String, bool)> {
+        let mut child = self.git()
             .args(args.iter().filter(|&arg| !arg.is_empty()))
+            .envs(envs.iter().copied())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()
@@ -145,24 +590,20 @@ impl GitService {
 
         let stdout_thread = std::thread::spawn(move || {
             let mut output = String::new();
-            for line in stdout_reader.lines() {
-                if let Ok(line) = line {
-                    println!("{}", line);
-                    output.push_str(&line);
-                    output.push('\n');
-                }
+            for line in stdout_reader.lines().map_while(std::result::Result::ok) {
+                println!("{}", line);
+                output.push_str(&line);
+                output.push('\n');
             }
             output
         });
 
         let stderr_thread = std::thread::spawn(move || {
             let mut output = String::new();
-            for line in stderr_reader.lines() {
-                if let Ok(line) = line {
-                    eprintln!("{}", line);
-                    output.push_str(&line);
-                    output.push('\n');
-                }
+            for line in stderr_reader.lines().map_while(std::result::Result::ok) {
+                eprintln!("{}", line);
+                output.push_str(&line);
+                output.push('\n');
             }
             output
         });
@@ -178,14 +619,161 @@ impl GitService {
         Ok((combined_output, status.success()))
     }
 
+    /// Runs a git command quietly and returns its stdout, failing with the
+    /// command's stderr when it exits unsuccessfully.
+    fn run_command(&self, args: &[&str], error_message: &str) -> Result<String> {
+        let output = self.git()
+            .args(args)
+            .output()
+            .map_err(|e| GitCliError::GitCommandFailed(format!("{}: {}", error_message, e)))?;
+
+        if !output.status.success() {
+            let error_msg = String::from_utf8_lossy(&output.stderr);
+            return Err(GitCliError::GitCommandFailed(format!("{}: {}", error_message, error_msg.trim())));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Runs a git command with `input` on stdin and returns its raw stdout.
+    fn run_command_with_input(&self, args: &[&str], input: Vec<u8>, error_message: &str) -> Result<Vec<u8>> {
+        let mut child = self.git()
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| GitCliError::GitCommandFailed(format!("{}: {}", error_message, e)))?;
+
+        // Feed stdin from a thread so a full stdout pipe cannot deadlock us
+        let mut stdin = child.stdin.take().expect("Failed to capture stdin");
+        let writer = std::thread::spawn(move || std::io::Write::write_all(&mut stdin, &input));
+        let output = child.wait_with_output()
+            .map_err(|e| GitCliError::GitCommandFailed(format!("{}: {}", error_message, e)))?;
+        writer.join().expect("Failed to join stdin thread")?;
+
+        if !output.status.success() {
+            let error_msg = String::from_utf8_lossy(&output.stderr);
+            return Err(GitCliError::GitCommandFailed(format!("{}: {}", error_message, error_msg.trim())));
+        }
+        Ok(output.stdout)
+    }
+
+    /// Runs a git command quietly and only reports whether it succeeded.
+    fn run_check(&self, args: &[&str]) -> Result<bool> {
+        let status = self.git()
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map_err(|e| GitCliError::GitCommandFailed(format!("Failed to execute git command: {}", e)))?;
+
+        Ok(status.success())
+    }
+
     pub fn commit(&self, title: &str, body: Option<&str>) -> Result<bool> {
-        let mut args = vec!["commit", "-m", title];
+        self.run_commit(&[], title, body)
+    }
+
+    /// Commits without git running the pre-commit hook, for when the
+    /// pre-commit phase already ran or is turned off. `--no-verify` skips the
+    /// commit-msg hook as well, so it is run here the way git would: on a
+    /// file holding the message, which the hook may rewrite or reject.
+    pub fn commit_without_pre_commit(&self, title: &str, body: Option<&str>) -> Result<bool> {
+        let hook = if self.verify { self.get_hook_path("commit-msg")? } else { None };
+        let Some(hook) = hook else {
+            return self.run_commit(&["--no-verify"], title, body);
+        };
+
+        let message_path = self.get_git_dir()?.join("git-cli").join("COMMIT_MSG");
+        if let Some(parent) = message_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let message = match body {
+            Some(body) => format!("{}\n\n{}\n", title, body),
+            None => format!("{}\n", title),
+        };
+        std::fs::write(&message_path, message)?;
+
+        debug!("Running commit-msg hook: {}", hook.display());
+        let status = Command::new(&hook)
+            .arg(&message_path)
+            .current_dir(self.get_work_tree_root()?)
+            .status()
+            .map_err(|e| GitCliError::GitCommandFailed(format!("Failed to run commit-msg hook: {}", e)))?;
+        if !status.success() {
+            warn!("commit-msg hook rejected the message");
+            let _ = std::fs::remove_file(&message_path);
+            return Ok(false);
+        }
+
+        let message_file = message_path.to_string_lossy().to_string();
+        let result = self.run_commit_with(&["--no-verify", "--file", &message_file]);
+        let _ = std::fs::remove_file(&message_path);
+        result
+    }
+
+    /// Rewrites the last commit. When `include_staged` is false only the
+    /// message is replaced and anything in the index is left staged.
+    pub fn amend(&self, title: &str, body: Option<&str>, include_staged: bool) -> Result<bool> {
+        if include_staged {
+            self.run_commit(&["--amend"], title, body)
+        } else {
+            self.run_commit(&["--amend", "--only"], title, body)
+        }
+    }
+
+    /// Creates a `fixup!`/`squash!` commit for `target` from the staged changes.
+    /// Squash commits carry `body` as the extra message to fold in.
+    pub fn commit_fixup(&self, kind: FixupKind, target: &str, body: Option<&str>) -> Result<bool> {
+        let option = match kind {
+            FixupKind::Fixup => format!("--fixup={}", target),
+            FixupKind::Squash => format!("--squash={}", target),
+        };
+
+        let mut args = vec!["commit", option.as_str()];
+        if !self.verify {
+            args.push("--no-verify");
+        }
+        match body {
+            Some(body) if kind == FixupKind::Squash => args.extend_from_slice(&["-m", body]),
+            _ => args.push("--no-edit"),
+        }
+
+        debug!("Running git commit with args: {:?}", args);
+        let (output, success) = self.run_command_stream(args, "Failed to create fixup commit")?;
+
+        if success {
+            info!("{} commit created for {}", kind.prefix(), target);
+        } else {
+            warn!("Fixup commit failed: {}", output);
+        }
+
+        Ok(success)
+    }
+
+    fn run_commit(&self, extra_args: &[&str], title: &str, body: Option<&str>) -> Result<bool> {
+        let mut args = extra_args.to_vec();
+        args.extend_from_slice(&["-m", title]);
         
         if let Some(body) = body {
             args.push("-m");
             args.push(body);
         }
 
+        self.run_commit_with(&args)
+    }
+
+    /// `git commit` with `extra_args`, which carry the message, under the
+    /// signing and verify settings.
+    fn run_commit_with(&self, extra_args: &[&str]) -> Result<bool> {
+        let signing_args = self.signing_args("commit");
+        let mut args: Vec<&str> = signing_args.iter().map(String::as_str).collect();
+        args.extend_from_slice(extra_args);
+        if !self.verify && !extra_args.contains(&"--no-verify") {
+            args.push("--no-verify");
+        }
+
         debug!("Running git commit with args: {:?}", args);
         let (output, success) = self.run_command_stream(args, "Failed to commit changes")?;
         
@@ -199,7 +787,7 @@ impl GitService {
     }
 
     pub fn get_current_branch(&self) -> Result<String> {
-        let output = Command::new("git")
+        let output = self.git()
             .arg("rev-parse")
             .arg("--abbrev-ref")
             .arg("HEAD")
@@ -216,11 +804,80 @@ impl GitService {
         Ok(branch_name)
     }
 
-    pub fn fetch_origin(&self, branch: &str) -> Result<()> {
-        let output = Command::new("git")
+    /// The branch's configured upstream (`branch.<name>.remote` and
+    /// `branch.<name>.merge`), or None for a branch that was never pushed.
+    pub fn get_upstream(&self, branch: &str) -> Result<Option<Upstream>> {
+        let remote = self.get_config(&format!("branch.{}.remote", branch))?;
+        let merge = self.get_config(&format!("branch.{}.merge", branch))?;
+
+        match (remote, merge) {
+            (Some(remote), Some(merge)) => Ok(Some(Upstream {
+                remote,
+                branch: merge.trim_start_matches("refs/heads/").to_string(),
+            })),
+            _ => Ok(None),
+        }
+    }
+
+    /// The remote `git push` would use for `branch`, following git's own
+    /// precedence so triangular workflows (fetch from upstream, push to a
+    /// fork) work: `branch.<name>.pushRemote`, `remote.pushDefault`,
+    /// `branch.<name>.remote`, then `origin` or the only configured remote.
+    pub fn get_push_remote(&self, branch: &str) -> Result<Option<String>> {
+        for key in [
+            format!("branch.{}.pushRemote", branch),
+            "remote.pushDefault".to_string(),
+            format!("branch.{}.remote", branch),
+        ] {
+            if let Some(remote) = self.get_config(&key)? {
+                return Ok(Some(remote));
+            }
+        }
+
+        let remotes = self.get_remotes()?;
+        if remotes.iter().any(|r| r == "origin") {
+            return Ok(Some("origin".to_string()));
+        }
+        if remotes.len() == 1 {
+            return Ok(remotes.into_iter().next());
+        }
+        Ok(None)
+    }
+
+    pub fn get_remotes(&self) -> Result<Vec<String>> {
+        let output = self.run_command(&["remote"], "Failed to list remotes")?;
+        Ok(output.lines().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect())
+    }
+
+    pub fn get_config(&self, key: &str) -> Result<Option<String>> {
+        let output = self.git()
+            .args(["config", "--get", key])
+            .output()
+            .map_err(|e| GitCliError::GitCommandFailed(format!("Failed to read config {}: {}", key, e)))?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(if value.is_empty() { None } else { Some(value) })
+    }
+
+    /// A boolean config value as git reads it (`yes`, `on`, `1`, ...); unset is false.
+    pub fn get_config_bool(&self, key: &str) -> Result<bool> {
+        let output = self.git()
+            .args(["config", "--type=bool", "--get", key])
+            .output()
+            .map_err(|e| GitCliError::GitCommandFailed(format!("Failed to read config {}: {}", key, e)))?;
+
+        Ok(output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "true")
+    }
+
+    pub fn fetch(&self, upstream: &Upstream) -> Result<()> {
+        let output = self.git()
             .arg("fetch")
-            .arg("origin")
-            .arg(branch)
+            .arg(&upstream.remote)
+            .arg(&upstream.branch)
             .output()
             .map_err(|e| GitCliError::GitCommandFailed(format!("Failed to fetch: {}", e)))?;
 
@@ -229,32 +886,41 @@ impl GitService {
             return Err(GitCliError::GitCommandFailed(format!("Fetch failed: {}", error_msg)));
         }
 
-        debug!("Fetched origin/{}", branch);
+        debug!("Fetched {}", upstream);
         Ok(())
     }
 
-    pub fn has_remote_changes(&self, branch: &str) -> Result<bool> {
-        let local_output = Command::new("git")
-            .arg("rev-parse")
-            .arg("HEAD")
-            .output()
-            .map_err(|e| GitCliError::GitCommandFailed(format!("Failed to get local commit: {}", e)))?;
-
-        let remote_output = Command::new("git")
-            .arg("rev-parse")
-            .arg(format!("origin/{}", branch))
-            .output()
-            .map_err(|e| GitCliError::GitCommandFailed(format!("Failed to get remote commit: {}", e)))?;
+    /// Counts commits on HEAD but not on the upstream (ahead) and vice versa
+    /// (behind) with `rev-list --left-right --count`.
+    pub fn get_divergence(&self) -> Result<Divergence> {
+        let output = self.run_command(&["rev-list", "--left-right", "--count", "HEAD...@{u}"], "Failed to compare with upstream")?;
+        Divergence::parse(&output)
+            .ok_or_else(|| GitCliError::GitCommandFailed(format!("Unexpected rev-list output: {}", output.trim())))
+    }
 
-        Ok(local_output.stdout != remote_output.stdout)
+    /// True only when the upstream has commits HEAD does not, so being merely
+    /// ahead after a local commit does not trigger a pull.
+    pub fn has_remote_changes(&self) -> Result<bool> {
+        Ok(self.get_divergence()?.behind > 0)
     }
 
-    pub fn pull(&self, branch: &str, use_rebase: bool) -> Result<()> {
-        let mut args = vec!["pull"];
-        if use_rebase {
-            args.push("--rebase");
+    /// Pulls from `upstream` using `strategy`. With `autostash`, git stashes
+    /// local changes around the pull and re-applies them afterwards.
+    pub fn pull(&self, upstream: &Upstream, strategy: PullStrategy, autostash: bool) -> Result<()> {
+        let signing_args = self.signing_args("pull");
+        let mut args: Vec<&str> = signing_args.iter().map(String::as_str).collect();
+        match strategy {
+            PullStrategy::Rebase => args.push("--rebase"),
+            PullStrategy::Merge => args.push("--no-rebase"),
+            PullStrategy::FfOnly => args.push("--ff-only"),
+            PullStrategy::Never => {
+                return Err(GitCliError::ConfigError("Pulling is disabled (sync.pull_strategy = never)".to_string()));
+            }
         }
-        args.extend_from_slice(&["origin", branch]);
+        if autostash {
+            args.push("--autostash");
+        }
+        args.extend_from_slice(&[upstream.remote.as_str(), upstream.branch.as_str()]);
 
         let (output, success) = self.run_command_stream(args, "Failed to pull changes")?;
         
@@ -262,26 +928,33 @@ impl GitService {
             return Err(GitCliError::GitCommandFailed(format!("Pull failed: {}", output)));
         }
 
-        info!("Successfully pulled changes");
+        info!("Successfully pulled changes from {}", upstream);
         Ok(())
     }
 
-    pub fn push(&self, branch: &str) -> Result<()> {
-        let (output, success) = self.run_command_stream(
-            vec!["push", "origin", branch], 
-            "Failed to push commits"
-        )?;
+    /// Pushes the local `branch` to `remote_branch` on `remote` with `options`.
+    /// A lease without an explicit expected hash is pinned to the current
+    /// remote-tracking ref, or to "must not exist" when there is none.
+    pub fn push(&self, remote: &str, branch: &str, remote_branch: &str, options: &PushOptions) -> Result<()> {
+        let tracked = match options.force {
+            ForceMode::WithLease { expected: None } => self.resolve_ref(&format!("refs/remotes/{}/{}", remote, remote_branch))?,
+            _ => None,
+        };
+        let args = options.to_args(remote, branch, remote_branch, tracked.as_deref());
+
+        debug!("Running git push with args: {:?}", args);
+        let (output, success) = self.run_command_stream(args.iter().map(|a| a.as_str()).collect(), "Failed to push commits")?;
         
         if !success {
             return Err(GitCliError::GitCommandFailed(format!("Push failed: {}", output)));
         }
 
-        info!("Successfully pushed to origin/{}", branch);
+        info!("Successfully pushed to {}/{}", remote, remote_branch);
         Ok(())
     }
 
     pub fn stash(&self) -> Result<()> {
-        let output = Command::new("git")
+        let output = self.git()
             .arg("stash")
             .output()
             .map_err(|e| GitCliError::GitCommandFailed(format!("Failed to stash: {}", e)))?;
@@ -296,7 +969,7 @@ impl GitService {
     }
 
     pub fn stash_pop(&self) -> Result<()> {
-        let output = Command::new("git")
+        let output = self.git()
             .arg("stash")
             .arg("pop")
             .output()
@@ -312,7 +985,7 @@ impl GitService {
     }
 
     pub fn get_recent_commits(&self, count: usize) -> Result<Vec<String>> {
-        let output = Command::new("git")
+        let output = self.git()
             .arg("log")
             .arg("--oneline")
             .arg(format!("-{}", count))
@@ -330,4 +1003,914 @@ impl GitService {
         debug!("Retrieved {} recent commits", commits.len());
         Ok(commits)
     }
+
+    pub fn get_head_message(&self) -> Result<String> {
+        let message = self.run_command(&["log", "-1", "--format=%B"], "Failed to read last commit")?;
+        Ok(message.trim_end().to_string())
+    }
+
+    pub fn get_head_hash(&self) -> Result<String> {
+        let hash = self.run_command(&["rev-parse", "--short", "HEAD"], "Failed to resolve HEAD")?;
+        Ok(hash.trim().to_string())
+    }
+
+    /// Returns true when HEAD is already contained in the branch's upstream.
+    /// Branches without an upstream are considered unpushed.
+    pub fn is_head_pushed(&self) -> Result<bool> {
+        if !self.has_upstream()? {
+            debug!("No upstream configured for current branch");
+            return Ok(false);
+        }
+
+        self.run_check(&["merge-base", "--is-ancestor", "HEAD", "@{u}"])
+    }
+
+    pub fn has_staged_changes(&self) -> Result<bool> {
+        Ok(!self.run_check(&["diff", "--cached", "--quiet"])?)
+    }
+
+    pub fn has_upstream(&self) -> Result<bool> {
+        self.run_check(&["rev-parse", "--verify", "--quiet", "@{u}"])
+    }
+
+    /// Lists commits that are on HEAD but not on the upstream, newest first.
+    /// Without an upstream every recent commit counts as outgoing.
+    pub fn get_outgoing_commits(&self, count: usize) -> Result<Vec<String>> {
+        if !self.has_upstream()? {
+            return self.get_recent_commits(count);
+        }
+
+        let limit = format!("-{}", count);
+        let log_output = self.run_command(&["log", "--oneline", &limit, "@{u}..HEAD"], "Failed to get outgoing commits")?;
+        let commits: Vec<String> = log_output.lines().map(|s| s.to_string()).collect();
+
+        debug!("Retrieved {} outgoing commits", commits.len());
+        Ok(commits)
+    }
+
+    /// Runs a non-interactive `rebase -i --autosquash` onto the upstream. The
+    /// rebase is aborted when it stops on a conflict, leaving HEAD untouched.
+    pub fn autosquash(&self) -> Result<()> {
+        if !self.has_upstream()? {
+            return Err(GitCliError::GitCommandFailed("No upstream configured for the current branch".to_string()));
+        }
+
+        let signing_args = self.signing_args("rebase");
+        let mut args: Vec<&str> = signing_args.iter().map(String::as_str).collect();
+        args.extend_from_slice(&["-i", "--autosquash", "--autostash", "@{u}"]);
+
+        let (output, success) = self.run_command_stream_with_env(
+            args,
+            // squash! commits would otherwise open an editor for the combined message
+            &[("GIT_SEQUENCE_EDITOR", "true"), ("GIT_EDITOR", "true")],
+            "Failed to run autosquash rebase",
+        )?;
+
+        if !success {
+            if self.is_rebase_in_progress()? {
+                self.rebase_abort()?;
+            }
+            return Err(GitCliError::GitCommandFailed(format!("Autosquash rebase failed and was aborted: {}", output.trim())));
+        }
+
+        info!("Autosquash rebase completed");
+        Ok(())
+    }
+
+    pub fn is_rebase_in_progress(&self) -> Result<bool> {
+        for dir in ["rebase-merge", "rebase-apply"] {
+            let path = self.run_command(&["rev-parse", "--git-path", dir], "Failed to locate git directory")?;
+            if self.resolve_path(path.trim()).exists() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    pub fn rebase_abort(&self) -> Result<()> {
+        self.run_command(&["rebase", "--abort"], "Failed to abort rebase")?;
+        info!("Rebase aborted");
+        Ok(())
+    }
+
+    pub fn get_work_tree_root(&self) -> Result<PathBuf> {
+        match &self.work_tree {
+            Some(root) => Ok(root.clone()),
+            None => self.discover_work_tree(),
+        }
+    }
+
+    /// Asks git for the root of the work tree git commands run in, even when
+    /// one was already set.
+    pub fn discover_work_tree(&self) -> Result<PathBuf> {
+        let root = self.run_command(&["rev-parse", "--show-toplevel"], "Failed to locate work tree")?;
+        Ok(PathBuf::from(root.trim()))
+    }
+
+    /// The installed hook script `name`, if there is an executable one.
+    pub fn get_hook_path(&self, name: &str) -> Result<Option<PathBuf>> {
+        let path = self.run_command(&["rev-parse", "--git-path", &format!("hooks/{}", name)], "Failed to locate hooks")?;
+        let path = self.resolve_path(path.trim());
+
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            return Ok(None);
+        };
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if metadata.permissions().mode() & 0o111 == 0 {
+                return Ok(None);
+            }
+        }
+        Ok(metadata.is_file().then_some(path))
+    }
+
+    pub fn find_pre_commit_hook(&self) -> Result<Option<PreCommitHook>> {
+        let installed = self.get_hook_path("pre-commit")?;
+        Ok(hooks::detect_pre_commit(&self.get_work_tree_root()?, installed))
+    }
+
+    /// Runs the pre-commit phase from the work tree root with its output shown
+    /// as-is, and reports whether it passed.
+    pub fn run_pre_commit_hook(&self, hook: &PreCommitHook) -> Result<bool> {
+        let (program, args) = hook
+            .command
+            .split_first()
+            .ok_or_else(|| GitCliError::GitCommandFailed("Empty hook command".to_string()))?;
+
+        debug!("Running {} hook: {:?}", hook.runner, hook.command);
+        let status = Command::new(program)
+            .args(args)
+            .current_dir(self.get_work_tree_root()?)
+            .status()
+            .map_err(|e| GitCliError::GitCommandFailed(format!("Failed to run {}: {}", program, e)))?;
+
+        Ok(status.success())
+    }
+
+    /// Runs a shell command from the work tree root and returns its combined
+    /// output and whether it succeeded.
+    pub fn run_shell(&self, command: &str) -> Result<(String, bool)> {
+        debug!("Running shell command: {}", command);
+        let output = Command::new("sh")
+            .args(["-c", command])
+            .current_dir(self.get_work_tree_root()?)
+            .output()
+            .map_err(|e| GitCliError::GitCommandFailed(format!("Failed to run {}: {}", command, e)))?;
+
+        let mut combined = String::from_utf8_lossy(&output.stdout).to_string();
+        combined.push_str(&String::from_utf8_lossy(&output.stderr));
+        Ok((combined, output.status.success()))
+    }
+
+    /// Staged files that still exist, i.e. everything but deletions and
+    /// submodule pointers. Read with `-z`, so names git would quote come
+    /// through as they are.
+    pub fn get_staged_files(&self) -> Result<Vec<String>> {
+        let output = self.run_command(
+            &["diff", "--cached", "--raw", "-z", "--no-renames", "--diff-filter=d"],
+            "Failed to list staged files",
+        )?;
+
+        // Each entry is ":<old mode> <new mode> <old id> <new id> <status>\0<path>\0"
+        let mut files = Vec::new();
+        let mut fields = output.split('\0');
+        while let (Some(header), Some(path)) = (fields.next(), fields.next()) {
+            let is_gitlink = header.split(' ').nth(1) == Some("160000");
+            if !path.is_empty() && !is_gitlink {
+                files.push(path.to_string());
+            }
+        }
+        Ok(files)
+    }
+
+    /// Hashes every file with staged, unstaged or untracked changes, to tell
+    /// afterwards which of them a hook rewrote.
+    pub fn snapshot_dirty_files(&self) -> Result<FileSnapshot> {
+        let mut paths = BTreeSet::new();
+        for args in [
+            ["diff", "--name-only", "--cached"].as_slice(),
+            &["diff", "--name-only"],
+            &["ls-files", "--others", "--exclude-standard"],
+        ] {
+            let mut args = args.to_vec();
+            args.splice(0..0, ["-c", "core.quotePath=false"]);
+            let output = self.run_command(&args, "Failed to list changed files")?;
+            paths.extend(output.lines().filter(|l| !l.is_empty()).map(str::to_string));
+        }
+
+        let (existing, missing): (Vec<String>, Vec<String>) =
+            paths.into_iter().partition(|path| self.resolve_path(path).is_file());
+
+        let mut snapshot = FileSnapshot::default();
+        snapshot.0.extend(missing.into_iter().map(|path| (path, None)));
+        if !existing.is_empty() {
+            let mut args = vec!["hash-object", "--"];
+            args.extend(existing.iter().map(String::as_str));
+            let hashes = self.run_command(&args, "Failed to hash changed files")?;
+            snapshot.0.extend(existing.into_iter().zip(hashes.lines().map(|h| Some(h.to_string()))));
+        }
+        Ok(snapshot)
+    }
+
+    /// The unstaged changes to `paths`, as a stat followed by the patch.
+    pub fn get_unstaged_diff(&self, paths: &[String]) -> Result<String> {
+        let mut args = vec!["diff", "--stat", "--patch", "--"];
+        args.extend(paths.iter().map(String::as_str));
+        self.run_command(&args, "Failed to show changes")
+    }
+
+    pub fn get_git_dir(&self) -> Result<PathBuf> {
+        let git_dir = self.run_command(&["rev-parse", "--absolute-git-dir"], "Failed to locate git directory")?;
+        Ok(PathBuf::from(git_dir.trim()))
+    }
+
+    /// Resolves `rev` to a full hash, or None when it does not exist (e.g. HEAD
+    /// before the first commit).
+    pub fn resolve_ref(&self, rev: &str) -> Result<Option<String>> {
+        let output = self.git()
+            .args(["rev-parse", "--verify", "--quiet", rev])
+            .output()
+            .map_err(|e| GitCliError::GitCommandFailed(format!("Failed to resolve {}: {}", rev, e)))?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        Ok(Some(String::from_utf8_lossy(&output.stdout).trim().to_string()))
+    }
+
+    /// Writes the current index as a tree object so it can be restored later.
+    pub fn write_tree(&self) -> Result<String> {
+        let tree = self.run_command(&["write-tree"], "Failed to snapshot the index")?;
+        Ok(tree.trim().to_string())
+    }
+
+    pub fn read_tree(&self, tree: &str) -> Result<()> {
+        self.run_command(&["read-tree", tree], "Failed to restore the index")?;
+        debug!("Restored index to tree {}", tree);
+        Ok(())
+    }
+
+    /// Unstages `paths`, leaving their working tree content alone.
+    pub fn unstage_files(&self, paths: &[String]) -> Result<()> {
+        let mut args = vec!["reset", "-q", "--"];
+        args.extend(paths.iter().map(String::as_str));
+        self.run_command(&args, "Failed to unstage files")?;
+        Ok(())
+    }
+
+    /// Stages `paths` as they were in `tree`, an index snapshot from `write_tree`.
+    pub fn restore_index_entries(&self, tree: &str, paths: &[String]) -> Result<()> {
+        let mut args = vec!["reset", "-q", tree, "--"];
+        args.extend(paths.iter().map(String::as_str));
+        self.run_command(&args, "Failed to restore staged files")?;
+        Ok(())
+    }
+
+    /// The size in bytes of the staged content of each path, or None when it
+    /// cannot be read: `cat-file` reports the entry missing, or the name holds
+    /// a newline and cannot be asked for at all.
+    pub fn get_staged_sizes(&self, paths: &[String]) -> Result<Vec<(String, Option<u64>)>> {
+        let (readable, unreadable): (Vec<&String>, Vec<&String>) = paths.iter().partition(|path| !path.contains('\n'));
+        let input: String = readable.iter().map(|path| format!(":{}\n", path)).collect();
+        let output = self.run_command_with_input(
+            &["cat-file", "--batch-check=%(objectsize)"],
+            input.into_bytes(),
+            "Failed to read staged file sizes",
+        )?;
+
+        let mut sizes: Vec<(String, Option<u64>)> = readable
+            .into_iter()
+            .zip(String::from_utf8_lossy(&output).lines())
+            .map(|(path, size)| (path.clone(), size.parse().ok()))
+            .collect();
+        sizes.extend(unreadable.into_iter().map(|path| (path.clone(), None)));
+        Ok(sizes)
+    }
+
+    /// The staged content of each path, as the commit would record it. Paths
+    /// that cannot be read are left out; see `get_staged_sizes`.
+    pub fn read_staged_blobs(&self, paths: &[String]) -> Result<Vec<(String, Vec<u8>)>> {
+        let paths: Vec<&String> = paths.iter().filter(|path| !path.contains('\n')).collect();
+        let input: String = paths.iter().map(|path| format!(":{}\n", path)).collect();
+        let output = self.run_command_with_input(
+            &["cat-file", "--batch=%(objectsize)"],
+            input.into_bytes(),
+            "Failed to read staged files",
+        )?;
+
+        // Each entry is "<size>\n<content>\n", or "<name> missing\n"
+        let mut blobs = Vec::new();
+        let mut rest = output.as_slice();
+        for path in paths {
+            let Some(end) = rest.iter().position(|&b| b == b'\n') else {
+                break;
+            };
+            let header = String::from_utf8_lossy(&rest[..end]).to_string();
+            rest = &rest[end + 1..];
+            if let Ok(size) = header.parse::<usize>() {
+                let size = size.min(rest.len());
+                blobs.push((path.clone(), rest[..size].to_vec()));
+                rest = rest.get(size + 1..).unwrap_or_default();
+            }
+        }
+        Ok(blobs)
+    }
+
+    pub fn is_lfs_available(&self) -> bool {
+        self.run_check(&["lfs", "version"]).unwrap_or(false)
+    }
+
+    /// Tracks `path` with Git LFS and stages it again, so the commit records
+    /// an LFS pointer instead of the content.
+    pub fn lfs_track(&self, path: &str) -> Result<()> {
+        self.run_command(&["lfs", "track", "--filename", path], "Failed to track file with Git LFS")?;
+        self.run_command(&["add", "--", ".gitattributes", path], "Failed to stage LFS pointer")?;
+        info!("Tracking {} with Git LFS", path);
+        Ok(())
+    }
+
+    /// What the next commit contains, per file.
+    pub fn get_staged_changes(&self) -> Result<Vec<StagedFile>> {
+        let output = self.run_command(
+            &["-c", "core.quotePath=false", "diff", "--cached", "--name-status"],
+            "Failed to list staged changes",
+        )?;
+        Ok(output.lines().filter_map(StagedFile::parse).collect())
+    }
+
+    /// Moves HEAD back to `rev`, keeping the index and working tree.
+    pub fn reset_soft(&self, rev: &str) -> Result<()> {
+        self.run_command(&["reset", "--soft", rev], "Failed to reset HEAD")?;
+        info!("Reset HEAD to {}", rev);
+        Ok(())
+    }
+
+    /// Moves HEAD and the working tree back to `rev`, refusing to discard local changes.
+    pub fn reset_keep(&self, rev: &str) -> Result<()> {
+        self.run_command(&["reset", "--keep", rev], "Failed to reset HEAD")?;
+        info!("Reset HEAD and working tree to {}", rev);
+        Ok(())
+    }
+
+    /// Removes the branch's only commit, keeping its content staged.
+    pub fn unborn_head(&self) -> Result<()> {
+        self.run_command(&["update-ref", "-d", "HEAD"], "Failed to remove root commit")?;
+        info!("Removed root commit");
+        Ok(())
+    }
+
+    pub fn get_stash_head(&self) -> Result<Option<String>> {
+        self.resolve_ref("refs/stash")
+    }
+
+    /// Returns the position of a stash commit in `git stash list`, if it is still there.
+    pub fn find_stash(&self, stash_ref: &str) -> Result<Option<usize>> {
+        let list = self.run_command(&["stash", "list", "--format=%H"], "Failed to list stashes")?;
+        Ok(list.lines().position(|hash| hash.trim() == stash_ref))
+    }
+
+    pub fn stash_pop_index(&self, index: usize) -> Result<()> {
+        let stash = format!("stash@{{{}}}", index);
+        self.run_command(&["stash", "pop", "--index", &stash], "Stash pop failed")?;
+        info!("Successfully popped {}", stash);
+        Ok(())
+    }
+
+    pub fn get_conflict_state(&self) -> Result<ConflictState> {
+        if self.is_rebase_in_progress()? {
+            return Ok(ConflictState::Rebase);
+        }
+        if self.resolve_ref("MERGE_HEAD")?.is_some() {
+            return Ok(ConflictState::Merge);
+        }
+        Ok(ConflictState::None)
+    }
+
+    pub fn get_conflicted_files(&self) -> Result<Vec<String>> {
+        let output = self.run_command(&["diff", "--name-only", "--diff-filter=U"], "Failed to list conflicted files")?;
+        Ok(output.lines().map(|s| s.to_string()).collect())
+    }
+
+    /// Resolves a conflicted file by taking one side wholesale and staging it.
+    /// When that side deleted the file (a delete/modify conflict), taking it
+    /// means removing the file.
+    pub fn checkout_conflict_side(&self, file: &str, side: ConflictSide) -> Result<()> {
+        let (side_arg, stage) = match side {
+            ConflictSide::Ours => ("--ours", 2),
+            ConflictSide::Theirs => ("--theirs", 3),
+        };
+        if self.resolve_ref(&format!(":{}:{}", stage, file))?.is_none() {
+            self.run_command(&["rm", "--quiet", "--", file], "Failed to remove deleted file")?;
+            debug!("Resolved {} with {} by removing it", file, side_arg);
+            return Ok(());
+        }
+        self.run_command(&["checkout", side_arg, "--", file], "Failed to check out conflict side")?;
+        self.run_command(&["add", "--", file], "Failed to stage resolved file")?;
+        debug!("Resolved {} with {}", file, side_arg);
+        Ok(())
+    }
+
+    pub fn mark_resolved(&self, file: &str) -> Result<()> {
+        self.run_command(&["add", "--", file], "Failed to mark file as resolved")?;
+        debug!("Marked {} as resolved", file);
+        Ok(())
+    }
+
+    /// Commits still waiting to be replayed by an interrupted rebase, as
+    /// `git-rebase-todo` lines (e.g. `pick abc1234 ✨ Add feature`).
+    pub fn get_pending_rebase_commits(&self) -> Result<Vec<String>> {
+        self.read_rebase_todo("rebase-merge/git-rebase-todo")
+    }
+
+    /// The rebase step that stopped, i.e. the last line of the `done` list.
+    pub fn get_current_rebase_commit(&self) -> Result<Option<String>> {
+        Ok(self.read_rebase_todo("rebase-merge/done")?.pop())
+    }
+
+    fn read_rebase_todo(&self, name: &str) -> Result<Vec<String>> {
+        let todo_path = self.run_command(&["rev-parse", "--git-path", name], "Failed to locate rebase todo")?;
+        let todo = match std::fs::read_to_string(self.resolve_path(todo_path.trim())) {
+            Ok(todo) => todo,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        Ok(todo
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.to_string())
+            .collect())
+    }
+
+    /// Continues the interrupted rebase or merge without opening an editor. A
+    /// rebase step whose resolution left nothing to commit is skipped.
+    pub fn continue_operation(&self, state: ConflictState) -> Result<bool> {
+        let args = match state {
+            ConflictState::Rebase if !self.has_staged_changes()? => vec!["rebase", "--skip"],
+            ConflictState::Rebase => vec!["rebase", "--continue"],
+            ConflictState::Merge => vec!["merge", "--continue"],
+            ConflictState::None => return Ok(true),
+        };
+
+        let (output, success) = self.run_command_stream_with_env(args, &[("GIT_EDITOR", "true")], "Failed to continue")?;
+        if !success {
+            debug!("Continue stopped: {}", output);
+        }
+        Ok(success)
+    }
+
+    pub fn abort_operation(&self, state: ConflictState) -> Result<()> {
+        match state {
+            ConflictState::Rebase => self.rebase_abort(),
+            ConflictState::Merge => {
+                self.run_command(&["merge", "--abort"], "Failed to abort merge")?;
+                info!("Merge aborted");
+                Ok(())
+            }
+            ConflictState::None => Ok(()),
+        }
+    }
+
+    pub fn has_conflict_markers(&self, file: &str) -> bool {
+        std::fs::read_to_string(self.resolve_path(file))
+            .map(|content| {
+                content
+                    .lines()
+                    .any(|line| line.starts_with("<<<<<<< ") || line.starts_with(">>>>>>> "))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Opens `file` in the user's configured git editor and waits for it to close.
+    pub fn open_in_editor(&self, file: &str) -> Result<()> {
+        let editor = self.run_command(&["var", "GIT_EDITOR"], "Failed to determine editor")?;
+        let mut command = Command::new("sh");
+        if let Some(root) = &self.work_tree {
+            command.current_dir(root);
+        }
+        let status = command
+            .arg("-c")
+            .arg(format!("{} \"$@\"", editor.trim()))
+            .arg("editor")
+            .arg(file)
+            .status()
+            .map_err(|e| GitCliError::GitCommandFailed(format!("Failed to launch editor: {}", e)))?;
+
+        if !status.success() {
+            return Err(GitCliError::GitCommandFailed(format!("Editor exited with {}", status)));
+        }
+        Ok(())
+    }
+
+    pub fn is_valid_branch_name(&self, name: &str) -> Result<bool> {
+        self.run_check(&["check-ref-format", "--branch", name])
+    }
+
+    /// Creates `name` at HEAD and switches to it, keeping the index and
+    /// working tree as they are.
+    pub fn create_branch(&self, name: &str) -> Result<()> {
+        self.run_command(&["switch", "-c", name], "Failed to create branch")?;
+        info!("Created and switched to branch {}", name);
+        Ok(())
+    }
+
+    pub fn switch_branch(&self, name: &str) -> Result<()> {
+        self.run_command(&["switch", name], "Failed to switch branch")?;
+        info!("Switched to branch {}", name);
+        Ok(())
+    }
+
+    pub fn delete_branch(&self, name: &str, force: bool) -> Result<()> {
+        let flag = if force { "-D" } else { "-d" };
+        self.run_command(&["branch", flag, name], "Failed to delete branch")?;
+        info!("Deleted branch {}", name);
+        Ok(())
+    }
+
+    /// Points `branch` at `target` without touching the working tree. Only
+    /// valid for branches that are not checked out.
+    pub fn move_branch(&self, branch: &str, target: &str) -> Result<()> {
+        self.run_command(&["branch", "-f", branch, target], "Failed to move branch")?;
+        info!("Moved {} to {}", branch, target);
+        Ok(())
+    }
+
+    /// Recreates a branch at `target`, e.g. to restore a deleted one.
+    pub fn create_branch_at(&self, name: &str, target: &str) -> Result<()> {
+        self.run_command(&["branch", name, target], "Failed to create branch")?;
+        info!("Created branch {} at {}", name, target);
+        Ok(())
+    }
+
+    /// Local branches, most recently committed to first.
+    pub fn get_local_branches(&self) -> Result<Vec<String>> {
+        let output = self.run_command(
+            &["for-each-ref", "--sort=-committerdate", "--format=%(refname:short)", "refs/heads"],
+            "Failed to list branches",
+        )?;
+        Ok(output.lines().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect())
+    }
+
+    /// Whether tracked files differ from HEAD, in the index or the working tree.
+    pub fn has_uncommitted_changes(&self) -> Result<bool> {
+        // On an unborn branch there is no HEAD to diff against, only the index
+        if self.resolve_ref("HEAD")?.is_none() {
+            return Ok(!self.run_check(&["diff", "--cached", "--quiet"])? || !self.run_check(&["diff", "--quiet"])?);
+        }
+        Ok(!self.run_check(&["diff", "--quiet", "HEAD"])?)
+    }
+
+    /// Stashes tracked changes under `message` and returns the stash commit.
+    pub fn stash_push(&self, message: &str) -> Result<String> {
+        self.stash_push_paths(message, &[], false)
+    }
+
+    /// Stashes the changes to `paths` (everything when empty) under `message`
+    /// and returns the stash commit.
+    pub fn stash_push_paths(&self, message: &str, paths: &[String], include_untracked: bool) -> Result<String> {
+        let previous = self.get_stash_head()?;
+        let mut args = vec!["stash", "push", "--message", message];
+        if include_untracked {
+            args.push("--include-untracked");
+        }
+        if !paths.is_empty() {
+            args.push("--");
+            args.extend(paths.iter().map(String::as_str));
+        }
+
+        self.run_command(&args, "Stash failed")?;
+        self.get_stash_head()?
+            .filter(|head| Some(head) != previous.as_ref())
+            .ok_or_else(|| GitCliError::GitCommandFailed("Stash failed: nothing was stashed".to_string()))
+    }
+
+    /// Hides unstaged and untracked changes in a `--keep-index` stash, so the
+    /// working tree only holds what is staged. Returns the stash commit, or
+    /// None when there was nothing to hide.
+    pub fn stash_unstaged(&self, message: &str) -> Result<Option<String>> {
+        let untracked = self.run_command(&["ls-files", "--others", "--exclude-standard"], "Failed to list untracked files")?;
+        if self.run_check(&["diff", "--quiet", "--ignore-submodules"])? && untracked.trim().is_empty() {
+            return Ok(None);
+        }
+
+        let previous = self.get_stash_head()?;
+        self.run_command(
+            &["stash", "push", "--keep-index", "--include-untracked", "--message", message],
+            "Failed to stash unstaged changes",
+        )?;
+        Ok(self.get_stash_head()?.filter(|head| Some(head) != previous.as_ref()))
+    }
+
+    /// Brings back the changes hidden by `stash_unstaged` as unstaged changes,
+    /// merged with whatever was rewritten in the meantime. Returns the files
+    /// left conflicted, to be settled with `take_conflict_side`; the stash
+    /// itself stays on the list.
+    pub fn restore_unstaged(&self, stash: &str) -> Result<Vec<String>> {
+        // Untracked files come back as they were, so refuse before touching anything
+        let untracked_commit = format!("{}^3", stash);
+        let untracked: Vec<String> = match self.resolve_ref(&untracked_commit)? {
+            Some(_) => self
+                .run_command(&["-c", "core.quotePath=false", "ls-tree", "-r", "--name-only", &untracked_commit], "Failed to list stashed files")?
+                .lines()
+                .map(str::to_string)
+                .collect(),
+            None => Vec::new(),
+        };
+        let existing: Vec<&str> = untracked.iter().filter(|path| self.resolve_path(path).exists()).map(String::as_str).collect();
+        if !existing.is_empty() {
+            return Err(GitCliError::GitCommandFailed(format!("Untracked files were recreated meanwhile: {}", existing.join(", "))));
+        }
+
+        // Rewrites nobody staged must be in the index for a three-way apply
+        if !self.run_check(&["diff", "--quiet", "--ignore-submodules"])? {
+            self.run_command(&["add", "--update"], "Failed to stage rewritten files")?;
+        }
+
+        // Only the unstaged part: the stashed working tree against the stashed index
+        let index_commit = format!("{}^2", stash);
+        let changed: Vec<String> = self
+            .run_command(&["-c", "core.quotePath=false", "diff", "--name-only", &index_commit, stash], "Failed to list stashed changes")?
+            .lines()
+            .map(str::to_string)
+            .collect();
+        let mut conflicted = Vec::new();
+        if !changed.is_empty() {
+            let patch = self.git()
+                .args(["diff", "--binary", &index_commit, stash])
+                .output()
+                .map_err(|e| GitCliError::GitCommandFailed(format!("Failed to read stashed changes: {}", e)))?;
+            let patch_path = self.get_git_dir()?.join("git-cli").join("unstaged.patch");
+            if let Some(parent) = patch_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&patch_path, &patch.stdout)?;
+
+            let applied = self.run_command(
+                &["apply", "--3way", &patch_path.to_string_lossy()],
+                "Failed to restore unstaged changes",
+            );
+            let _ = std::fs::remove_file(&patch_path);
+            if let Err(e) = applied {
+                conflicted = self.get_conflicted_files()?;
+                if conflicted.is_empty() {
+                    return Err(e);
+                }
+            }
+
+            // --3way stages what it applies, but these were unstaged changes
+            let clean: Vec<String> = changed.into_iter().filter(|f| !conflicted.contains(f)).collect();
+            if !clean.is_empty() {
+                self.unstage_files(&clean)?;
+            }
+        }
+
+        if !untracked.is_empty() {
+            self.run_command(&["checkout", &untracked_commit, "--", "."], "Failed to restore untracked files")?;
+            self.unstage_files(&untracked)?;
+        }
+
+        info!("Restored unstaged changes from {}", stash);
+        Ok(conflicted)
+    }
+
+    /// Settles a file left conflicted by `restore_unstaged` with one side,
+    /// leaving the result unstaged.
+    pub fn take_conflict_side(&self, file: &str, side: ConflictSide) -> Result<()> {
+        let side_arg = match side {
+            ConflictSide::Ours => "--ours",
+            ConflictSide::Theirs => "--theirs",
+        };
+        self.run_command(&["checkout", side_arg, "--", file], "Failed to check out conflict side")?;
+        self.run_command(&["reset", "-q", "--", file], "Failed to unstage resolved file")?;
+        Ok(())
+    }
+
+    /// The stash list, newest first.
+    pub fn get_stashes(&self) -> Result<Vec<StashEntry>> {
+        let output = self.run_command(&["stash", "list", "--format=%gd%x1f%H%x1f%cr%x1f%gs"], "Failed to list stashes")?;
+        Ok(output.lines().filter_map(StashEntry::parse).collect())
+    }
+
+    /// Diffstat of a stash, followed by the full patch when `patch` is set.
+    pub fn get_stash_diff(&self, reference: &str, patch: bool) -> Result<String> {
+        let mut args = vec!["stash", "show", "--include-untracked", "--stat"];
+        if patch {
+            args.push("--patch");
+        }
+        args.push(reference);
+        self.run_command(&args, "Failed to show stash")
+    }
+
+    /// Applies a stash to the working tree, removing it from the list when `pop` is set.
+    pub fn stash_apply(&self, reference: &str, pop: bool) -> Result<()> {
+        let action = if pop { "pop" } else { "apply" };
+        self.run_command(&["stash", action, reference], &format!("Stash {} failed", action))?;
+        info!("Successfully applied {}", reference);
+        Ok(())
+    }
+
+    pub fn stash_drop(&self, reference: &str) -> Result<()> {
+        self.run_command(&["stash", "drop", reference], "Failed to drop stash")?;
+        info!("Dropped {}", reference);
+        Ok(())
+    }
+
+    /// Puts a dropped stash commit back on the stash list.
+    pub fn stash_store(&self, hash: &str, message: &str) -> Result<()> {
+        self.run_command(&["stash", "store", "--message", message, hash], "Failed to restore stash")?;
+        info!("Restored stash {}", hash);
+        Ok(())
+    }
+
+    /// The branch `<remote>/HEAD` points at, falling back to a local `main`
+    /// or `master` when no remote advertises one.
+    pub fn get_default_branch(&self) -> Result<Option<String>> {
+        for remote in self.get_remotes()? {
+            let head_ref = format!("refs/remotes/{}/HEAD", remote);
+            if let Ok(target) = self.run_command(&["symbolic-ref", "--short", &head_ref], "Failed to read remote HEAD") {
+                if let Some(branch) = target.trim().strip_prefix(&format!("{}/", remote)) {
+                    return Ok(Some(branch.to_string()));
+                }
+            }
+        }
+
+        for candidate in ["main", "master"] {
+            if self.resolve_ref(&format!("refs/heads/{}", candidate))?.is_some() {
+                return Ok(Some(candidate.to_string()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Local branches whose tips are reachable from `base`.
+    pub fn get_merged_branches(&self, base: &str) -> Result<Vec<String>> {
+        let output = self.run_command(
+            &["for-each-ref", "--format=%(refname:short)", "--merged", base, "refs/heads"],
+            "Failed to list merged branches",
+        )?;
+        Ok(output.lines().map(|s| s.trim().to_string()).filter(|s| !s.is_empty() && s != base).collect())
+    }
+
+    /// Local branches whose upstream branch no longer exists on the remote.
+    pub fn get_gone_branches(&self) -> Result<Vec<String>> {
+        let output = self.run_command(
+            &["for-each-ref", "--format=%(refname:short)%09%(upstream:track)", "refs/heads"],
+            "Failed to list branches",
+        )?;
+        Ok(output
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .filter(|(_, track)| track.trim() == "[gone]")
+            .map(|(name, _)| name.to_string())
+            .collect())
+    }
+
+    /// Fetches every remote and prunes remote-tracking branches that were deleted.
+    pub fn fetch_prune(&self) -> Result<()> {
+        self.run_command(&["fetch", "--all", "--prune", "--quiet"], "Failed to fetch")?;
+        info!("Fetched and pruned all remotes");
+        Ok(())
+    }
+
+    /// Tags reachable from `rev`.
+    pub fn get_merged_tags(&self, rev: &str) -> Result<Vec<String>> {
+        let output = self.run_command(&["tag", "--merged", rev], "Failed to list tags")?;
+        Ok(output.lines().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect())
+    }
+
+    /// Hashes and full messages of the non-merge commits in `from..to` (all
+    /// of `to`'s history without `from`), newest first.
+    pub fn get_commits_between(&self, from: Option<&str>, to: &str) -> Result<Vec<(String, String)>> {
+        let range = from.map_or_else(|| to.to_string(), |from| format!("{}..{}", from, to));
+        let output = self.run_command(&["log", "--no-merges", "--format=%H%x1f%B%x1e", &range], "Failed to list commits")?;
+
+        Ok(output
+            .split('\x1e')
+            .filter_map(|entry| entry.trim_start().split_once('\x1f'))
+            .map(|(hash, message)| (hash.to_string(), message.trim().to_string()))
+            .collect())
+    }
+
+    /// Committer date of `rev` as YYYY-MM-DD.
+    pub fn get_commit_date(&self, rev: &str) -> Result<String> {
+        let output = self.run_command(&["log", "-1", "--format=%cs", rev], "Failed to read commit date")?;
+        Ok(output.trim().to_string())
+    }
+
+    /// Creates an annotated tag at HEAD, signed with the configured key when
+    /// `sign` is set. The `[signing]` mode overrides `sign` unless it inherits,
+    /// in which case git's `tag.gpgSign` still applies to unsigned requests.
+    pub fn create_tag(&self, name: &str, message: &str, sign: bool) -> Result<()> {
+        let sign = match self.signing.mode {
+            SigningMode::Always => true,
+            SigningMode::Never => false,
+            SigningMode::Inherit => sign,
+        };
+
+        let mut args = self.signing_config_args();
+        args.push("tag".to_string());
+        match (&self.signing.key, sign) {
+            (Some(key), true) => args.push(format!("--local-user={}", key)),
+            (None, true) => args.push("--sign".to_string()),
+            (_, false) => args.push("--annotate".to_string()),
+        }
+        if self.signing.mode == SigningMode::Never {
+            args.push("--no-sign".to_string());
+        }
+        args.extend([name.to_string(), "--message".to_string(), message.to_string()]);
+
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        self.run_command(&args, "Failed to create tag")?;
+        info!("Created tag {}", name);
+        Ok(())
+    }
+
+    pub fn delete_tag(&self, name: &str) -> Result<()> {
+        self.run_command(&["tag", "--delete", name], "Failed to delete tag")?;
+        info!("Deleted tag {}", name);
+        Ok(())
+    }
+
+    pub fn push_tag(&self, remote: &str, name: &str) -> Result<()> {
+        let refspec = format!("refs/tags/{}", name);
+        self.run_command(&["push", remote, &refspec], "Failed to push tag")?;
+        info!("Pushed tag {} to {}", name, remote);
+        Ok(())
+    }
+
+    /// Submodules with new commits or uncommitted changes, including the
+    /// commits each would bring into the parent.
+    pub fn get_submodules(&self) -> Result<Vec<Submodule>> {
+        let root = self.get_work_tree_root()?;
+        let mut submodules = self.get_changed_submodules()?;
+
+        for submodule in &mut submodules {
+            let dir = root.join(&submodule.path).to_string_lossy().to_string();
+            submodule.head = self
+                .run_command(&["-C", &dir, "rev-parse", "--verify", "--quiet", "HEAD"], "Failed to resolve submodule HEAD")
+                .ok()
+                .map(|hash| hash.trim().to_string())
+                .filter(|hash| !hash.is_empty());
+
+            // The recorded commit may not have been fetched into the submodule
+            if let (Some(recorded), Some(head)) = (&submodule.recorded, &submodule.head) {
+                let range = format!("{}..{}", recorded, head);
+                submodule.new_commits = self
+                    .run_command(&["-C", &dir, "log", "--format=%h %s", &range], "Failed to list submodule commits")
+                    .map(|output| output.lines().map(str::to_string).collect())
+                    .unwrap_or_default();
+            }
+        }
+
+        debug!("Found {} changed submodules", submodules.len());
+        Ok(submodules)
+    }
+
+    fn get_changed_submodules(&self) -> Result<Vec<Submodule>> {
+        let output = self.run_command(
+            &["-c", "status.relativePaths=false", "status", "--porcelain=v2", "--ignore-submodules=none"],
+            "Failed to inspect submodules",
+        )?;
+        Ok(output.lines().filter_map(Submodule::parse_status).collect())
+    }
+
+    /// Signature status of `rev`.
+    pub fn get_signature(&self, rev: &str) -> Result<CommitSignature> {
+        let signatures = self.get_signatures(&["-1", rev])?;
+        signatures
+            .into_iter()
+            .next()
+            .ok_or_else(|| GitCliError::GitCommandFailed(format!("Commit {} not found", rev)))
+    }
+
+    /// Signature status of the commits that would be pushed: those after the
+    /// current branch's `upstream`, or those on no remote at all for a branch
+    /// without one. The range goes through `@{u}`, since the `remote/branch`
+    /// form names no ref for a local (`.`) upstream.
+    pub fn get_outgoing_signatures(&self, upstream: Option<&Upstream>) -> Result<Vec<CommitSignature>> {
+        match upstream {
+            Some(_) => self.get_signatures(&["@{u}..HEAD"]),
+            None => self.get_signatures(&["HEAD", "--not", "--remotes"]),
+        }
+    }
+
+    fn get_signatures(&self, revs: &[&str]) -> Result<Vec<CommitSignature>> {
+        let mut args = vec!["log", "--format=%h%x1f%G?%x1f%GS%x1f%s"];
+        args.extend_from_slice(revs);
+        let output = self.run_command(&args, "Failed to verify signatures")?;
+
+        Ok(output
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(4, '\x1f');
+                let hash = fields.next()?.to_string();
+                let status = SignatureStatus::parse(fields.next()?);
+                let signer = fields.next().filter(|s| !s.is_empty()).map(str::to_string);
+                let subject = fields.next().unwrap_or_default().to_string();
+                Some(CommitSignature { hash, subject, status, signer })
+            })
+            .collect())
+    }
 }
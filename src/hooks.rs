@@ -0,0 +1,123 @@
+use crate::config::Fixer;
+use crate::utils;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// The tool a repository manages its pre-commit hooks with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HookRunner {
+    PreCommit,
+    Husky,
+    Lefthook,
+    /// A plain script in the hooks directory.
+    Git,
+}
+
+impl fmt::Display for HookRunner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HookRunner::PreCommit => write!(f, "pre-commit"),
+            HookRunner::Husky => write!(f, "husky"),
+            HookRunner::Lefthook => write!(f, "lefthook"),
+            HookRunner::Git => write!(f, "git hook"),
+        }
+    }
+}
+
+const LEFTHOOK_FILES: &[&str] = &["lefthook.yml", ".lefthook.yml", "lefthook.yaml", ".lefthook.yaml"];
+
+/// The pre-commit phase of a repository and the command that runs it from
+/// the work tree root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreCommitHook {
+    pub runner: HookRunner,
+    pub command: Vec<String>,
+}
+
+/// Finds the pre-commit hook of the repository at `root`. The installed hook
+/// script (`installed`) is preferred, since it is what `git commit` would run;
+/// otherwise the runner's own command is used when its config is present.
+pub fn detect_pre_commit(root: &Path, installed: Option<PathBuf>) -> Option<PreCommitHook> {
+    let runner = if root.join(".pre-commit-config.yaml").is_file() {
+        HookRunner::PreCommit
+    } else if root.join(".husky").join("pre-commit").is_file() {
+        HookRunner::Husky
+    } else if LEFTHOOK_FILES.iter().any(|file| root.join(file).is_file()) {
+        HookRunner::Lefthook
+    } else {
+        HookRunner::Git
+    };
+
+    let command: Vec<String> = match (installed, runner) {
+        (Some(path), _) => vec![path.to_string_lossy().to_string()],
+        (None, HookRunner::PreCommit) => vec!["pre-commit".into(), "run".into()],
+        (None, HookRunner::Husky) => vec!["sh".into(), ".husky/pre-commit".into()],
+        (None, HookRunner::Lefthook) => vec!["lefthook".into(), "run".into(), "pre-commit".into()],
+        (None, HookRunner::Git) => return None,
+    };
+
+    Some(PreCommitHook { runner, command })
+}
+
+/// Content hashes of the files with pending changes, keyed by path; None
+/// for files that do not exist on disk.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileSnapshot(pub BTreeMap<String, Option<String>>);
+
+impl FileSnapshot {
+    /// Files whose content differs between this snapshot and `after`,
+    /// including files that appeared or disappeared in between.
+    pub fn changed_files(&self, after: &FileSnapshot) -> Vec<String> {
+        let mut changed: Vec<String> = after
+            .0
+            .iter()
+            .filter(|(path, hash)| self.0.get(*path) != Some(*hash))
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        // Files that only had pending changes before were reverted
+        changed.extend(self.0.keys().filter(|path| !after.0.contains_key(*path)).cloned());
+        changed.sort();
+        changed
+    }
+}
+
+/// The files each fixer applies to, in config order; fixers matching none
+/// of `files` are left out. A file can be handled by several fixers.
+pub fn match_fixers<'a>(fixers: &'a [Fixer], files: &[String]) -> Vec<(&'a Fixer, Vec<String>)> {
+    fixers
+        .iter()
+        .filter_map(|fixer| {
+            let matching: Vec<String> = files.iter().filter(|file| utils::glob_match(&fixer.glob, file)).cloned().collect();
+            (!matching.is_empty()).then_some((fixer, matching))
+        })
+        .collect()
+}
+
+/// The shell command that runs `fixer` on `files`.
+pub fn fixer_command(fixer: &Fixer, files: &[String]) -> String {
+    let quoted: Vec<String> = files.iter().map(|file| shell_quote(file)).collect();
+    let quoted = quoted.join(" ");
+    if fixer.command.contains("{files}") {
+        fixer.command.replace("{files}", &quoted)
+    } else {
+        format!("{} {}", fixer.command, quoted)
+    }
+}
+
+fn shell_quote(text: &str) -> String {
+    if !text.is_empty() && text.chars().all(|c| c.is_ascii_alphanumeric() || "-_./+@=:,".contains(c)) {
+        text.to_string()
+    } else {
+        format!("'{}'", text.replace('\'', "'\\''"))
+    }
+}
+
+/// What one fixer did to the staged files.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixerRun {
+    pub command: String,
+    pub files: usize,
+    pub changed: Vec<String>,
+}
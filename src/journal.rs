@@ -0,0 +1,177 @@
+use crate::errors::{GitCliError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Keep the journal small; only the latest sessions can be undone anyway.
+const MAX_SESSIONS: usize = 20;
+
+/// A single mutating step performed by git-cli, with enough state to roll it back.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JournalStep {
+    /// Files were staged; `index_tree` is the index before staging.
+    Stage { index_tree: String },
+    /// A commit was created on top of `orig_head` (None for the root commit).
+    Commit { orig_head: Option<String>, new_head: String },
+    Amend { orig_head: String, new_head: String },
+    Rebase { orig_head: String, new_head: String },
+    Pull { orig_head: String, new_head: String },
+    Stash { stash_ref: String },
+    StashPop { stash_ref: String },
+    /// A stash was dropped; `message` is kept so it can be stored again.
+    StashDrop { stash_ref: String, message: String },
+    Push { remote: String, branch: String, head: String },
+    /// Work was carried from `from` onto the newly created branch `created`.
+    SwitchBranch { from: String, created: String },
+    /// A branch that is not checked out was moved from `orig_head`.
+    MoveBranch { branch: String, orig_head: String, new_head: String },
+    /// HEAD was switched from one existing branch to another.
+    Switch { from: String, to: String },
+    /// A branch pointing at `head` was deleted.
+    DeleteBranch { branch: String, head: String },
+    /// An annotated tag was created.
+    Tag { name: String },
+}
+
+impl JournalStep {
+    pub fn describe(&self) -> String {
+        match self {
+            JournalStep::Stage { .. } => "stage files".to_string(),
+            JournalStep::Commit { new_head, .. } => format!("commit {}", short(new_head)),
+            JournalStep::Amend { orig_head, new_head } => format!("amend {} into {}", short(orig_head), short(new_head)),
+            JournalStep::Rebase { orig_head, new_head } => format!("rebase {} to {}", short(orig_head), short(new_head)),
+            JournalStep::Pull { orig_head, new_head } => format!("pull {} to {}", short(orig_head), short(new_head)),
+            JournalStep::Stash { stash_ref } => format!("stash {}", short(stash_ref)),
+            JournalStep::StashPop { stash_ref } => format!("pop stash {}", short(stash_ref)),
+            JournalStep::StashDrop { stash_ref, .. } => format!("drop stash {}", short(stash_ref)),
+            JournalStep::Push { remote, branch, head } => format!("push {} to {}/{}", short(head), remote, branch),
+            JournalStep::SwitchBranch { from, created } => format!("move work from {} to new branch {}", from, created),
+            JournalStep::MoveBranch { branch, orig_head, new_head } => {
+                format!("reset {} from {} to {}", branch, short(orig_head), short(new_head))
+            }
+            JournalStep::Switch { from, to } => format!("switch from {} to {}", from, to),
+            JournalStep::DeleteBranch { branch, head } => format!("delete branch {} at {}", branch, short(head)),
+            JournalStep::Tag { name } => format!("create tag {}", name),
+        }
+    }
+
+    /// Pushes are visible to others and cannot be rolled back locally.
+    pub fn is_undoable(&self) -> bool {
+        !matches!(self, JournalStep::Push { .. })
+    }
+
+    /// The HEAD this step left behind, for steps that move HEAD.
+    pub fn resulting_head(&self) -> Option<&str> {
+        match self {
+            JournalStep::Commit { new_head, .. }
+            | JournalStep::Amend { new_head, .. }
+            | JournalStep::Rebase { new_head, .. }
+            | JournalStep::Pull { new_head, .. } => Some(new_head),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalSession {
+    pub command: String,
+    pub started_at: u64,
+    pub steps: Vec<JournalStep>,
+}
+
+impl JournalSession {
+    /// The HEAD expected at the end of the session, if any step moved it.
+    pub fn final_head(&self) -> Option<&str> {
+        self.steps.iter().rev().find_map(|step| step.resulting_head())
+    }
+}
+
+/// Sessions recorded under `.git/git-cli/journal.json`, oldest first.
+pub struct Journal {
+    path: PathBuf,
+}
+
+impl Journal {
+    pub fn new(git_dir: &Path) -> Self {
+        Self {
+            path: git_dir.join("git-cli").join("journal.json"),
+        }
+    }
+
+    pub fn start_session(&self, command: &str) -> Result<()> {
+        let mut sessions = self.load()?;
+
+        // A session that never mutated anything is not worth keeping
+        if sessions.last().is_some_and(|s| s.steps.is_empty()) {
+            sessions.pop();
+        }
+
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        sessions.push(JournalSession {
+            command: command.to_string(),
+            started_at,
+            steps: Vec::new(),
+        });
+
+        let overflow = sessions.len().saturating_sub(MAX_SESSIONS);
+        sessions.drain(..overflow);
+
+        self.save(&sessions)
+    }
+
+    /// Appends a step to the current session. Steps are written immediately so
+    /// an interrupted run can still be undone.
+    pub fn record(&self, step: JournalStep) -> Result<()> {
+        let mut sessions = self.load()?;
+        let session = sessions
+            .last_mut()
+            .ok_or_else(|| GitCliError::ConfigError("No journal session started".to_string()))?;
+
+        session.steps.push(step);
+        self.save(&sessions)
+    }
+
+    /// The most recent session that performed at least one step.
+    pub fn last_session(&self) -> Result<Option<JournalSession>> {
+        let sessions = self.load()?;
+        Ok(sessions.into_iter().rev().find(|s| !s.steps.is_empty()))
+    }
+
+    pub fn remove_last_session(&self) -> Result<()> {
+        let mut sessions = self.load()?;
+        while sessions.last().is_some_and(|s| s.steps.is_empty()) {
+            sessions.pop();
+        }
+        sessions.pop();
+        self.save(&sessions)
+    }
+
+    fn load(&self) -> Result<Vec<JournalSession>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let journal_str = std::fs::read_to_string(&self.path)?;
+        let sessions = serde_json::from_str(&journal_str)?;
+        Ok(sessions)
+    }
+
+    fn save(&self, sessions: &[JournalSession]) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let journal_str = serde_json::to_string_pretty(sessions)?;
+        std::fs::write(&self.path, journal_str)?;
+        Ok(())
+    }
+}
+
+fn short(hash: &str) -> &str {
+    &hash[..hash.len().min(7)]
+}
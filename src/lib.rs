@@ -1,10 +1,20 @@
+pub mod branch;
+pub mod changelog;
 pub mod config;
 pub mod errors;
 pub mod emojis;
 pub mod git;
+pub mod hooks;
+pub mod journal;
+pub mod message;
+pub mod release;
+pub mod scan;
+pub mod stash;
 pub mod ui;
 pub mod utils;
 pub mod validation;
+pub mod version_files;
+pub mod workspace;
 
 pub use config::Config;
 pub use errors::{GitCliError, Result};
@@ -1,26 +1,105 @@
 use clap::{Arg, Command};
 use log::{info, warn, error, debug};
+use std::collections::BTreeSet;
 
-mod emojis;
-mod utils;
-mod errors;
-mod config;
-mod git;
-mod ui;
-mod validation;
+use git_cli::{branch, changelog, emojis, git, hooks, utils};
+use git_cli::errors::{Result, GitCliError};
+use git_cli::config::{Config, PullStrategy};
+use git_cli::git::{ConflictSide, ConflictState, FixupKind, ForceMode, GitService, PushOptions, SignatureStatus, StagedFile, Upstream};
+use git_cli::hooks::{FixerRun, PreCommitHook};
+use git_cli::journal::{Journal, JournalStep};
+use git_cli::message::{self, CommitMessage};
+use git_cli::release::{self, Bump, ReleaseCommit, Version};
+use git_cli::scan::{self, Allowlist, Finding, Scanner};
+use git_cli::stash::{self, LeftoverStashes, StashEntry};
+use git_cli::ui::{ConflictAction, ConflictNextStep, StagedExtrasAction, UIService};
+use git_cli::validation::ValidationService;
+use git_cli::version_files::{self, VersionFileState, VersionFormat};
+use git_cli::workspace::{self, RepoOutcome, WorkspaceRepo};
 
-use errors::{Result, GitCliError};
-use config::Config;
-use git::GitService;
-use ui::UIService;
+#[derive(Debug)]
+enum CliCommand {
+    Commit,
+    Amend,
+    Fixup { target: Option<String>, squash: bool },
+    Autosquash,
+    Undo,
+    Resolve,
+    Branch(BranchCommand),
+    Release { pre: Option<String>, bump: Option<Bump>, sign: bool, check: bool },
+    Changelog { from: Option<String>, to: String, json: bool, update: bool, file: Option<String> },
+    Stash(StashCommand),
+    Workspace { file: Option<String> },
+}
+
+#[derive(Debug)]
+enum BranchCommand {
+    New { description: Option<String>, commit_type: Option<String>, issue: Option<String> },
+    Switch { query: Option<String> },
+    Clean,
+}
+
+#[derive(Debug)]
+enum StashCommand {
+    Save { message: Option<String>, paths: Vec<String>, include_untracked: bool },
+    List,
+    Show { stash: Option<String> },
+    Apply { stash: Option<String> },
+    Pop { stash: Option<String> },
+    Drop { stash: Option<String> },
+}
+
+impl CliCommand {
+    fn name(&self) -> &'static str {
+        match self {
+            CliCommand::Commit => "commit",
+            CliCommand::Amend => "amend",
+            CliCommand::Fixup { .. } => "fixup",
+            CliCommand::Autosquash => "autosquash",
+            CliCommand::Undo => "undo",
+            CliCommand::Resolve => "resolve",
+            CliCommand::Branch(_) => "branch",
+            CliCommand::Release { .. } => "release",
+            CliCommand::Changelog { .. } => "changelog",
+            CliCommand::Stash(_) => "stash",
+            CliCommand::Workspace { .. } => "workspace",
+        }
+    }
+}
 
 #[derive(Debug)]
 struct AppConfig {
+    command: CliCommand,
     debug: bool,
     non_interactive: bool,
     emoji: Option<String>,
     title: Option<String>,
     body: Option<String>,
+    force_with_lease: bool,
+    force: bool,
+    follow_tags: bool,
+    push_options: Vec<String>,
+    dry_run: bool,
+    directory: Option<String>,
+    no_verify: bool,
+    no_scan: bool,
+}
+
+/// Staged changes set aside so that a partial selection is committed on its
+/// own; they are staged again from `index_tree` once the commit is made.
+struct HeldBack {
+    index_tree: String,
+    files: Vec<String>,
+}
+
+/// A workspace repository with the files picked for the shared commit;
+/// no files means all changes.
+struct WorkspaceCommit {
+    index: usize,
+    repo: WorkspaceRepo,
+    git_service: GitService,
+    changes: Vec<git::Change>,
+    files: Vec<String>,
 }
 
 struct GitWorkflow {
@@ -33,7 +112,19 @@ struct GitWorkflow {
 impl GitWorkflow {
     fn new(app_config: AppConfig) -> Result<Self> {
         let config = Config::load()?;
-        let git_service = GitService::new(app_config.debug);
+        let git_service = GitService::new(app_config.debug)
+            .with_signing(config.signing.clone())
+            .with_verify(!app_config.no_verify);
+
+        // Git reports paths relative to the work tree root, so every command
+        // runs from there, whichever subdirectory git-cli was started in
+        let git_service = match git_service.get_work_tree_root() {
+            Ok(root) => git_service.with_work_tree(root),
+            Err(e) => {
+                debug!("No work tree found: {}", e);
+                git_service
+            }
+        };
         let ui_service = UIService::new(config.clone());
 
         Ok(Self {
@@ -44,201 +135,2330 @@ impl GitWorkflow {
         })
     }
 
+    async fn run(&self) -> Result<()> {
+        // Workspace mode runs against the repositories it lists, not the current one
+        if !matches!(self.app_config.command, CliCommand::Undo | CliCommand::Workspace { .. }) {
+            self.git_service.verify_git_initialized()?;
+            if let Err(e) = self.journal().and_then(|j| j.start_session(self.app_config.command.name())) {
+                warn!("Failed to start journal session: {}", e);
+            }
+            if let Err(e) = self.flag_leftover_stashes() {
+                warn!("Failed to check for leftover stashes: {}", e);
+            }
+        }
+
+        match &self.app_config.command {
+            CliCommand::Commit => self.execute().await,
+            CliCommand::Amend => self.execute_amend().await,
+            CliCommand::Fixup { target, squash } => self.execute_fixup(target.as_deref(), *squash).await,
+            CliCommand::Autosquash => self.execute_autosquash().await,
+            CliCommand::Undo => self.execute_undo().await,
+            CliCommand::Resolve => self.execute_resolve().await,
+            CliCommand::Branch(BranchCommand::New { description, commit_type, issue }) => {
+                self.execute_branch_new(description.as_deref(), commit_type.as_deref(), issue.as_deref())
+            }
+            CliCommand::Branch(BranchCommand::Switch { query }) => self.execute_branch_switch(query.as_deref()),
+            CliCommand::Branch(BranchCommand::Clean) => self.execute_branch_clean(),
+            CliCommand::Release { pre, bump, sign, check } => self.execute_release(pre.as_deref(), *bump, *sign, *check).await,
+            CliCommand::Changelog { from, to, json, update, file } => {
+                self.execute_changelog(from.as_deref(), to, *json, *update, file.as_deref())
+            }
+            CliCommand::Stash(StashCommand::Save { message, paths, include_untracked }) => {
+                self.execute_stash_save(message.as_deref(), paths, *include_untracked)
+            }
+            CliCommand::Stash(StashCommand::List) => self.execute_stash_list(),
+            CliCommand::Stash(StashCommand::Show { stash }) => self.execute_stash_show(stash.as_deref()),
+            CliCommand::Stash(StashCommand::Apply { stash }) => self.execute_stash_apply(stash.as_deref(), false),
+            CliCommand::Stash(StashCommand::Pop { stash }) => self.execute_stash_apply(stash.as_deref(), true),
+            CliCommand::Stash(StashCommand::Drop { stash }) => self.execute_stash_drop(stash.as_deref()),
+            CliCommand::Workspace { file } => self.execute_workspace(file.as_deref()).await,
+        }
+    }
+
+    fn journal(&self) -> Result<Journal> {
+        Ok(Journal::new(&self.git_service.get_git_dir()?))
+    }
+
+    fn record(&self, step: JournalStep) {
+        self.record_in(&self.git_service, step)
+    }
+
+    /// Journals a step in `git_service`'s repository; failing to record must
+    /// never fail the operation itself.
+    fn record_in(&self, git_service: &GitService, step: JournalStep) {
+        debug!("Recording journal step: {}", step.describe());
+        if let Err(e) = git_service.get_git_dir().and_then(|dir| Journal::new(&dir).record(step)) {
+            warn!("Failed to record journal step: {}", e);
+        }
+    }
+
+    fn leftover_stashes(&self) -> Result<LeftoverStashes> {
+        Ok(LeftoverStashes::new(&self.git_service.get_git_dir()?))
+    }
+
+    /// Reminds about stashes a failed pull left behind until they are
+    /// applied or dropped.
+    fn flag_leftover_stashes(&self) -> Result<()> {
+        let stashes = self.git_service.get_stashes()?;
+        for entry in self.leftover_stashes()?.remaining(&stashes)? {
+            self.ui_service.show_warning(&format!(
+                "{} still holds local changes git-cli could not re-apply. Run `git-cli stash pop {}` to restore them.",
+                entry, entry.index
+            ));
+        }
+        Ok(())
+    }
+
+    fn head(&self) -> Option<String> {
+        self.git_service.resolve_ref("HEAD").ok().flatten()
+    }
+
+    /// Stages `files` (everything when empty), journaling the previous index.
+    fn add_files(&self, files: &[String]) -> Result<()> {
+        self.add_files_in(&self.git_service, files)
+    }
+
+    fn add_files_in(&self, git_service: &GitService, files: &[String]) -> Result<()> {
+        match git_service.write_tree() {
+            Ok(index_tree) => self.record_in(git_service, JournalStep::Stage { index_tree }),
+            Err(e) => warn!("Failed to snapshot index before staging: {}", e),
+        }
+        git_service.add_files(files)
+    }
+
     async fn execute(&self) -> Result<()> {
         info!("Starting git-cli workflow");
 
         // Verify git repository
         self.git_service.verify_git_initialized()?;
 
-        // Get changes
-        let changes = self.git_service.get_status()?;
-        if changes.is_empty() {
-            self.ui_service.show_info("No changes to commit.");
+        // Get changes
+        let changes = self.git_service.get_status()?;
+        if changes.is_empty() {
+            self.ui_service.show_info("No changes to commit.");
+            return Ok(());
+        }
+
+        // Submodules are settled first, so their pointers get a commit of their own
+        let mut committed_submodules = false;
+        let changes = if changes.iter().any(git::Change::is_submodule) {
+            committed_submodules = self.commit_submodules().await?;
+            self.get_file_changes()?
+        } else {
+            changes
+        };
+        if changes.is_empty() {
+            if committed_submodules {
+                self.handle_remote_operations().await?;
+                self.show_commit_summary().await?;
+            } else {
+                self.ui_service.show_info("No file changes to commit.");
+            }
+            return Ok(());
+        }
+
+        self.ui_service.show_changes(&changes);
+
+        // Offer to split the working tree into one commit per group of related files
+        let mut changes = changes;
+        let mut first = true;
+        if self.allows_multiple_commits() {
+            let groups = ValidationService::group_changes(&changes);
+            if groups.len() > 1 && self.ui_service.confirm_split_into_groups(&groups)? {
+                for group in &groups {
+                    if !self.ui_service.confirm_commit_group(group)? {
+                        continue;
+                    }
+
+                    let files = group.files();
+                    let prefill = CommitMessage {
+                        emoji: group.emoji.map(|e| e.to_string()),
+                        title: String::new(),
+                        body: None,
+                    };
+                    // Files staged outside the group are held back, not swept into its commit
+                    self.with_index_rollback(async {
+                        self.add_files(&files)?;
+                        self.check_interrupted()?;
+                        let held_back = self.review_staged_selection(&files)?;
+                        self.commit_staged(&group.changes, Some(&prefill)).await?;
+                        self.restage_held_back(held_back)
+                    })
+                    .await?;
+                }
+
+                changes = self.get_file_changes()?;
+                first = false;
+            }
+        }
+
+        // Commit the rest one selection at a time, as long as the user wants to continue
+        while !changes.is_empty() {
+            if !first {
+                self.ui_service.show_changes(&changes);
+                if !self.ui_service.confirm_commit_remaining(changes.len())? {
+                    break;
+                }
+            }
+            first = false;
+
+            // Stage files, restoring the index if the commit does not happen
+            self.with_index_rollback(async {
+                let held_back = self.stage_files(&changes).await?;
+                self.commit_staged(&changes, None).await?;
+                self.restage_held_back(held_back)
+            })
+            .await?;
+
+            if !self.allows_multiple_commits() {
+                break;
+            }
+            changes = self.get_file_changes()?;
+        }
+
+        // Handle remote operations
+        self.handle_remote_operations().await?;
+
+        // Show recent commits
+        self.show_commit_summary().await?;
+
+        Ok(())
+    }
+
+    /// The working tree changes without submodule entries, which are
+    /// committed separately by `commit_submodules`.
+    fn get_file_changes(&self) -> Result<Vec<git::Change>> {
+        let mut changes = self.git_service.get_status()?;
+        changes.retain(|change| !change.is_submodule());
+        Ok(changes)
+    }
+
+    /// Shows changed submodules, offers to run the commit flow inside dirty
+    /// ones, then commits each updated pointer with a message listing the
+    /// commits it brings in. Returns whether any pointer was committed.
+    async fn commit_submodules(&self) -> Result<bool> {
+        let submodules = self.git_service.get_submodules()?;
+        self.ui_service.show_submodules(&submodules);
+        if self.app_config.non_interactive {
+            self.ui_service.show_warning("Leaving submodule changes unstaged; run git-cli interactively to review them");
+            return Ok(false);
+        }
+
+        let root = self.git_service.get_work_tree_root()?;
+        for submodule in submodules.iter().filter(|s| s.is_dirty()) {
+            if self.ui_service.confirm_commit_in_submodule(&submodule.path)? {
+                self.run_in_submodule(&root.join(&submodule.path))?;
+            }
+        }
+
+        // Committing inside a submodule moved its HEAD, so look again
+        let mut committed = false;
+        for submodule in self.git_service.get_submodules()?.into_iter().filter(|s| s.commit_changed) {
+            let (title, body) = submodule.update_message();
+            let emoji = if submodule.new_commits.is_empty() { "📌" } else { "⬆️" };
+            if !self.ui_service.confirm_submodule_commit(&submodule.path, &format!("{} {}", emoji, title), body.as_deref())? {
+                continue;
+            }
+
+            let files = vec![submodule.path.clone()];
+            self.with_index_rollback(async {
+                self.add_files(&files)?;
+                let held_back = self.hold_back_all_but(&files)?;
+                self.create_commit(emoji, &title, body.as_deref()).await?;
+                self.restage_held_back(held_back)
+            })
+            .await?;
+            committed = true;
+        }
+        Ok(committed)
+    }
+
+    /// Runs git-cli in `dir` as a child process, so the submodule gets its
+    /// own journal, hooks and push prompt.
+    fn run_in_submodule(&self, dir: &std::path::Path) -> Result<()> {
+        let mut command = std::process::Command::new(std::env::current_exe()?);
+        command.current_dir(dir);
+        if self.app_config.debug {
+            command.arg("--debug");
+        }
+
+        let status = command.status()?;
+        if !status.success() {
+            self.ui_service.show_warning(&format!("git-cli did not finish in {}", dir.display()));
+        }
+        Ok(())
+    }
+
+    /// Runs a stage-and-commit `operation`, restoring the index to the tree it
+    /// had beforehand when the operation fails or is cancelled.
+    async fn with_index_rollback<T>(&self, operation: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+        let snapshot = match self.git_service.write_tree() {
+            Ok(tree) => Some(tree),
+            Err(e) => {
+                warn!("Failed to snapshot index, changes will not be rolled back: {}", e);
+                None
+            }
+        };
+
+        let result = operation.await;
+
+        if let (Err(e), Some(tree)) = (&result, snapshot) {
+            debug!("Rolling back index after: {}", e);
+            match self.git_service.read_tree(&tree) {
+                Ok(()) => self.ui_service.show_warning("Restored the staging area to its state before git-cli ran"),
+                Err(restore_error) => self.ui_service.show_error(&format!("Failed to restore the staging area: {}", restore_error)),
+            }
+        }
+
+        result
+    }
+
+    fn check_interrupted(&self) -> Result<()> {
+        if utils::is_interrupted() {
+            return Err(GitCliError::UserCancelled);
+        }
+        Ok(())
+    }
+
+    /// Multi-commit sessions are interactive only; flags describe a single commit.
+    fn allows_multiple_commits(&self) -> bool {
+        !self.app_config.non_interactive && self.app_config.title.is_none()
+    }
+
+    async fn commit_staged(
+        &self,
+        changes: &[git::Change],
+        prefill: Option<&CommitMessage>,
+    ) -> Result<()> {
+        // Get commit details
+        let (emoji, title, body) = self.get_commit_details(changes, prefill).await?;
+        self.check_interrupted()?;
+
+        self.create_commit(&emoji.emoji, &title, body.as_deref()).await
+    }
+
+    /// Commits the staged changes, moving them off a protected branch first.
+    async fn create_commit(&self, emoji: &str, title: &str, body: Option<&str>) -> Result<()> {
+        self.guard_protected_branch(Some((emoji, title)))?;
+        self.scan_staged()?;
+
+        // Create commit message
+        let commit_title = format!("{} {}", emoji, title);
+        
+        // Attempt commit
+        let orig_head = self.head();
+        let commit_successful = self.attempt_commit(&commit_title, body).await?;
+        
+        if !commit_successful {
+            return Err(GitCliError::GitCommandFailed("Commit failed".to_string()));
+        }
+
+        if let Some(new_head) = self.head() {
+            self.record(JournalStep::Commit { orig_head, new_head });
+        }
+
+        self.ui_service.show_success(&format!("Successfully committed with emoji: {}", emoji));
+        Ok(())
+    }
+
+    async fn execute_amend(&self) -> Result<()> {
+        info!("Starting git-cli amend workflow");
+
+        self.git_service.verify_git_initialized()?;
+
+        let emojis_object = emojis::get_emojis()?;
+        let previous = CommitMessage::parse(&self.git_service.get_head_message()?, &emojis_object);
+        self.guard_protected_branch(Some((previous.emoji.as_deref().unwrap_or_default(), &previous.title)))?;
+        let head = self.git_service.get_head_hash()?;
+
+        let was_pushed = self.git_service.is_head_pushed()?;
+        let lease_expected = self.git_service.resolve_ref("@{u}")?;
+        if was_pushed {
+            if self.app_config.non_interactive {
+                return Err(GitCliError::AlreadyPushed(head));
+            }
+            if !self.ui_service.confirm_amend_pushed_commit(&head)? {
+                return Err(GitCliError::UserCancelled);
+            }
+        }
+
+        self.ui_service.show_info(&format!("Amending {}: {}", head, previous.subject()));
+
+        let (emoji, orig_head, held_back) = self.with_index_rollback(async {
+            // Fold working tree changes into the commit only when asked to
+            let changes = self.git_service.get_status()?;
+            let mut held_back = None;
+            let include_staged = if self.app_config.non_interactive {
+                self.git_service.has_staged_changes()?
+            } else if !changes.is_empty() {
+                self.ui_service.show_changes(&changes);
+                if self.ui_service.confirm_include_changes_in_amend()? {
+                    held_back = self.stage_files(&changes).await?;
+                    true
+                } else {
+                    false
+                }
+            } else {
+                false
+            };
+
+            let (emoji, title, body) = self.get_commit_details(&changes, Some(&previous)).await?;
+            self.check_interrupted()?;
+            if include_staged {
+                self.scan_staged()?;
+            }
+            let commit_title = format!("{} {}", emoji.emoji, title);
+
+            let orig_head = self.head();
+            if !self.git_service.amend(&commit_title, body.as_deref(), include_staged)? {
+                return Err(GitCliError::GitCommandFailed("Amend failed".to_string()));
+            }
+            Ok((emoji, orig_head, held_back))
+        })
+        .await?;
+
+        if let (Some(orig_head), Some(new_head)) = (orig_head, self.head()) {
+            self.record(JournalStep::Amend { orig_head, new_head });
+        }
+        self.restage_held_back(held_back)?;
+
+        self.ui_service.show_success(&format!("Successfully amended {} with emoji: {}", head, emoji.emoji));
+
+        if was_pushed {
+            self.offer_push_after_rewrite(lease_expected)?;
+        }
+
+        self.show_commit_summary().await?;
+
+        Ok(())
+    }
+
+    async fn execute_fixup(&self, target: Option<&str>, squash: bool) -> Result<()> {
+        info!("Starting git-cli fixup workflow");
+
+        self.git_service.verify_git_initialized()?;
+
+        let emojis_object = emojis::get_emojis()?;
+        let head = CommitMessage::parse(&self.git_service.get_head_message()?, &emojis_object);
+        self.guard_protected_branch(Some((head.emoji.as_deref().unwrap_or_default(), &head.title)))?;
+
+        let commits = match target {
+            Some(_) => Vec::new(),
+            None if self.app_config.non_interactive => {
+                return Err(GitCliError::ValidationError("A --target commit is required in non-interactive mode".to_string()));
+            }
+            None => {
+                let commits = self.git_service.get_outgoing_commits(20)?;
+                if commits.is_empty() {
+                    self.ui_service.show_info("No outgoing commits to fix up.");
+                    return Ok(());
+                }
+                commits
+            }
+        };
+
+        let (kind, target, orig_head, held_back) = self.with_index_rollback(async {
+            // Use what is already staged, otherwise stage through the usual prompts
+            let mut held_back = None;
+            if !self.git_service.has_staged_changes()? {
+                let changes = self.git_service.get_status()?;
+                if changes.is_empty() {
+                    return Err(GitCliError::NoChanges);
+                }
+                self.ui_service.show_changes(&changes);
+                held_back = self.stage_files(&changes).await?;
+            }
+
+            let target = match target {
+                Some(target) => target.to_string(),
+                None => self.ui_service.select_fixup_target(&commits)?,
+            };
+
+            let kind = if squash {
+                FixupKind::Squash
+            } else if self.app_config.non_interactive {
+                FixupKind::Fixup
+            } else {
+                self.ui_service.select_fixup_kind()?
+            };
+
+            let body = match kind {
+                FixupKind::Squash if self.app_config.body.is_some() => self.app_config.body.clone(),
+                FixupKind::Squash if !self.app_config.non_interactive => self.ui_service.get_commit_message()?,
+                _ => None,
+            };
+            self.check_interrupted()?;
+            self.scan_staged()?;
+
+            let orig_head = self.head();
+            if !self.git_service.commit_fixup(kind, &target, body.as_deref())? {
+                return Err(GitCliError::GitCommandFailed("Commit failed".to_string()));
+            }
+            Ok((kind, target, orig_head, held_back))
+        })
+        .await?;
+
+        if let Some(new_head) = self.head() {
+            self.record(JournalStep::Commit { orig_head, new_head });
+        }
+        self.restage_held_back(held_back)?;
+
+        self.ui_service.show_success(&format!("Created {} commit for {}", kind.prefix(), target));
+        self.ui_service.show_info("Run `git-cli autosquash` to fold it into the target commit.");
+
+        self.show_commit_summary().await?;
+
+        Ok(())
+    }
+
+    async fn execute_autosquash(&self) -> Result<()> {
+        info!("Starting git-cli autosquash workflow");
+
+        self.git_service.verify_git_initialized()?;
+        // The rebase runs onto the upstream, which a new branch would not have
+        self.guard_protected_branch(None)?;
+
+        let outgoing = self.git_service.get_outgoing_commits(100)?;
+        let pending = outgoing
+            .iter()
+            .filter(|c| c.contains(FixupKind::Fixup.prefix()) || c.contains(FixupKind::Squash.prefix()))
+            .count();
+
+        if pending == 0 {
+            self.ui_service.show_info("No fixup! or squash! commits to fold in.");
+            return Ok(());
+        }
+
+        self.ui_service.show_info(&format!("Folding {} fixup/squash commits into their targets...", pending));
+
+        let lease_expected = self.git_service.resolve_ref("@{u}")?;
+        let orig_head = self.head();
+        match self.git_service.autosquash() {
+            Ok(()) => {
+                if let (Some(orig_head), Some(new_head)) = (orig_head, self.head()) {
+                    self.record(JournalStep::Rebase { orig_head, new_head });
+                }
+                self.ui_service.show_success("Autosquash completed")
+            }
+            Err(e) => {
+                self.ui_service.show_error(&format!("{}", e));
+                return Err(GitCliError::GitCommandFailed("Autosquash failed".to_string()));
+            }
+        }
+
+        self.offer_push_after_rewrite(lease_expected)?;
+
+        self.show_commit_summary().await?;
+
+        Ok(())
+    }
+
+    /// Stages all or a selection of `changes`. A selection is checked against
+    /// what the index really holds; files staged outside of it may be held
+    /// back from the commit, to be passed to `restage_held_back` afterwards.
+    async fn stage_files(&self, changes: &[git::Change]) -> Result<Option<HeldBack>> {
+        let (add_all, selected_files) = if self.app_config.non_interactive {
+            (true, changes.iter().map(|c| c.value.clone()).collect())
+        } else {
+            let add_all = self.ui_service.confirm_add_all_files()?;
+            let files = if add_all {
+                changes.iter().map(|c| c.value.clone()).collect()
+            } else {
+                self.ui_service.select_files_to_commit(changes)?
+            };
+            (add_all, files)
+        };
+
+        if selected_files.is_empty() {
+            return Err(GitCliError::NoChanges);
+        }
+
+        // Stage files
+        if add_all {
+            self.add_files(&[])?; // Empty slice means add all
+        } else {
+            self.add_files(&selected_files)?;
+        }
+
+        self.check_interrupted()?;
+        self.ui_service.show_info(&format!("Staged {} files", selected_files.len()));
+
+        if add_all {
+            return Ok(None);
+        }
+        self.review_staged_selection(&selected_files)
+    }
+
+    /// Shows what the commit will really contain and, when files staged before
+    /// git-cli ran are not part of `selection`, asks what to do with them.
+    fn review_staged_selection(&self, selection: &[String]) -> Result<Option<HeldBack>> {
+        let staged = self.git_service.get_staged_changes()?;
+        let extras: Vec<&StagedFile> = staged.iter().filter(|file| !file.is_selected(selection)).collect();
+        self.ui_service.show_staged_preview(&staged, &extras);
+        if extras.is_empty() {
+            return Ok(None);
+        }
+
+        let files: Vec<String> = extras.iter().flat_map(|file| file.paths()).collect();
+        match self.ui_service.select_staged_extras_action(extras.len())? {
+            StagedExtrasAction::CommitAll => Ok(None),
+            StagedExtrasAction::Unstage => {
+                self.unstage_files(&files)?;
+                Ok(None)
+            }
+            StagedExtrasAction::CommitSelectionOnly => self.hold_back(files).map(Some),
+        }
+    }
+
+    /// Like `review_staged_selection`, but always holds back what is staged
+    /// outside `selection`, for commits that must contain nothing else.
+    fn hold_back_all_but(&self, selection: &[String]) -> Result<Option<HeldBack>> {
+        let staged = self.git_service.get_staged_changes()?;
+        let extras: Vec<&StagedFile> = staged.iter().filter(|file| !file.is_selected(selection)).collect();
+        self.ui_service.show_staged_preview(&staged, &extras);
+        if extras.is_empty() {
+            return Ok(None);
+        }
+
+        let files: Vec<String> = extras.iter().flat_map(|file| file.paths()).collect();
+        self.ui_service.show_info(&format!("Keeping {} other staged files out of this commit", files.len()));
+        self.hold_back(files).map(Some)
+    }
+
+    fn hold_back(&self, files: Vec<String>) -> Result<HeldBack> {
+        let index_tree = self.git_service.write_tree()?;
+        self.unstage_files(&files)?;
+        Ok(HeldBack { index_tree, files })
+    }
+
+    fn unstage_files(&self, files: &[String]) -> Result<()> {
+        match self.git_service.write_tree() {
+            Ok(index_tree) => self.record(JournalStep::Stage { index_tree }),
+            Err(e) => warn!("Failed to snapshot index before unstaging: {}", e),
+        }
+        self.git_service.unstage_files(files)
+    }
+
+    /// Stages the files `stage_files` held back from the commit again.
+    fn restage_held_back(&self, held_back: Option<HeldBack>) -> Result<()> {
+        let Some(held_back) = held_back else {
+            return Ok(());
+        };
+        match self.git_service.write_tree() {
+            Ok(index_tree) => self.record(JournalStep::Stage { index_tree }),
+            Err(e) => warn!("Failed to snapshot index before restaging: {}", e),
+        }
+        self.git_service.restore_index_entries(&held_back.index_tree, &held_back.files)?;
+        self.ui_service.show_info(&format!("Staged {} held-back files again", held_back.files.len()));
+        Ok(())
+    }
+
+    /// Collects emoji, title and body from flags or prompts. When `previous`
+    /// is given (amend), its values prefill the prompts and are reused as-is
+    /// in non-interactive mode.
+    async fn get_commit_details(
+        &self,
+        changes: &[git::Change],
+        previous: Option<&CommitMessage>,
+    ) -> Result<(emojis::Emoji, String, Option<String>)> {
+        let emojis_object = emojis::get_emojis()?;
+        let previous_emoji = previous.and_then(|p| p.emoji.as_deref());
+
+        let emoji = if let Some(emoji_str) = &self.app_config.emoji {
+            message::find_emoji(&emojis_object, emoji_str)
+                .cloned()
+                .ok_or(GitCliError::InvalidEmoji)?
+        } else if self.app_config.non_interactive && previous.is_some() {
+            // A previous message without a known emoji gets the default one
+            let emoji_str = previous_emoji.unwrap_or(&self.config.general.default_emoji);
+            message::find_emoji(&emojis_object, emoji_str)
+                .cloned()
+                .ok_or(GitCliError::InvalidEmoji)?
+        } else {
+            self.ui_service.select_emoji_with_default(&emojis_object, previous_emoji)?
+        };
+
+        let title = if let Some(title) = &self.app_config.title {
+            title.clone()
+        } else if let (true, Some(previous)) = (self.app_config.non_interactive, previous) {
+            self.ui_service.validate_commit_title(&previous.title)?;
+            previous.title.clone()
+        } else {
+            let initial = previous.map(|p| p.title.as_str()).filter(|t| !t.is_empty());
+            self.ui_service.get_commit_title_with_initial(changes, initial)?
+        };
+
+        let body = if let Some(body) = &self.app_config.body {
+            Some(body.clone())
+        } else if !self.app_config.non_interactive {
+            self.ui_service.get_commit_message_with_initial(previous.and_then(|p| p.body.as_deref()))?
+        } else {
+            previous.and_then(|p| p.body.clone())
+        };
+
+        Ok((emoji, title, body))
+    }
+
+    async fn attempt_commit(&self, title: &str, body: Option<&str>) -> Result<bool> {
+        self.attempt_commit_in(&self.git_service, title, body).await
+    }
+
+    /// Runs the fixers and the pre-commit hooks in `git_service`'s repository
+    /// ahead of `git commit`, so the files they rewrite can be shown and
+    /// re-staged before committing.
+    async fn attempt_commit_in(&self, git_service: &GitService, title: &str, body: Option<&str>) -> Result<bool> {
+        debug!("Attempting commit with title: {}", title);
+
+        if self.app_config.no_verify {
+            self.ui_service.show_warning("Skipping git hooks and fixers (--no-verify)");
+            return git_service.commit(title, body);
+        }
+
+        let fixers = self.config.hooks.auto_fix_lint && !self.config.fixers.is_empty();
+        let hook = if self.config.hooks.run_pre_commit {
+            git_service.find_pre_commit_hook().unwrap_or_else(|e| {
+                warn!("Failed to look up pre-commit hooks: {}", e);
+                None
+            })
+        } else {
+            None
+        };
+        if !fixers && hook.is_none() {
+            return self.commit_after_hooks(git_service, title, body, false);
+        }
+
+        // Fixers and hooks only get to see, and rewrite, what is being committed
+        let hidden = self.hide_unstaged_changes(git_service)?;
+        let result = self.run_pre_commit_phase(git_service, title, body, fixers, hook.as_ref());
+        if let Some(stash) = hidden {
+            if let Err(e) = self.restore_unstaged_changes(git_service, &stash) {
+                self.ui_service.show_error(&format!("Failed to restore unstaged changes: {}", e));
+            }
+        }
+        result
+    }
+
+    fn run_pre_commit_phase(&self, git_service: &GitService, title: &str, body: Option<&str>, fixers: bool, hook: Option<&PreCommitHook>) -> Result<bool> {
+        if fixers {
+            self.run_fixers(git_service)?;
+        }
+        let Some(hook) = hook else {
+            return self.commit_after_hooks(git_service, title, body, false);
+        };
+
+        for attempt in 1..=2 {
+            self.ui_service.show_info(&format!("Running pre-commit hooks ({})...", hook.runner));
+            let before = git_service.snapshot_dirty_files()?;
+            let passed = match git_service.run_pre_commit_hook(hook) {
+                Ok(passed) => passed,
+                Err(e) => {
+                    // e.g. the runner is configured but not installed; git decides
+                    self.ui_service.show_warning(&format!("Could not run pre-commit hooks: {}", e));
+                    return self.commit_after_hooks(git_service, title, body, false);
+                }
+            };
+            let touched = before.changed_files(&git_service.snapshot_dirty_files()?);
+
+            // Stage what the hooks rewrote, unless they failed and retrying is off
+            let restage = !touched.is_empty() && (passed || self.config.hooks.retry_on_failure);
+            if !touched.is_empty() {
+                let diff = git_service.get_unstaged_diff(&touched)?;
+                self.ui_service.show_hook_changes(&touched, &diff);
+            }
+            if restage {
+                self.add_files_in(git_service, &touched)?;
+                self.ui_service.show_info(&format!("Re-staged {} files rewritten by the hooks", touched.len()));
+            }
+
+            if passed {
+                break;
+            }
+            if !restage || attempt == 2 {
+                self.ui_service.show_error("Pre-commit hooks failed");
+                return Ok(false);
+            }
+            self.ui_service.show_warning("Pre-commit hooks failed. Running them again on the re-staged files...");
+        }
+
+        self.commit_after_hooks(git_service, title, body, true)
+    }
+
+    /// Commits, letting git run the pre-commit hook unless git-cli already ran
+    /// it or `hooks.run_pre_commit` is off. The commit-msg hook always runs.
+    fn commit_after_hooks(&self, git_service: &GitService, title: &str, body: Option<&str>, hooks_ran: bool) -> Result<bool> {
+        if hooks_ran || !self.config.hooks.run_pre_commit {
+            return git_service.commit_without_pre_commit(title, body);
+        }
+        git_service.commit(title, body)
+    }
+
+    /// Stashes unstaged and untracked changes so that fixers and hooks neither
+    /// rewrite them nor get them re-staged along with their fixes.
+    fn hide_unstaged_changes(&self, git_service: &GitService) -> Result<Option<String>> {
+        let stash = git_service.stash_unstaged("git-cli: unstaged changes hidden from pre-commit hooks")?;
+        if stash.is_some() {
+            self.ui_service.show_info("Hid unstaged changes from the pre-commit hooks");
+        }
+        Ok(stash)
+    }
+
+    /// Brings back the changes hidden by `hide_unstaged_changes`. Files the
+    /// hooks rewrote that also had unstaged changes are settled one by one;
+    /// the stash is dropped only when nothing from it was lost, otherwise it
+    /// is flagged on every run until restored.
+    fn restore_unstaged_changes(&self, git_service: &GitService, stash: &str) -> Result<()> {
+        let conflicted = match git_service.restore_unstaged(stash) {
+            Ok(conflicted) => conflicted,
+            Err(e) => {
+                self.keep_hidden_changes(git_service, stash)?;
+                return Err(e);
+            }
+        };
+
+        let mut kept_all = true;
+        if !conflicted.is_empty() {
+            self.ui_service.show_warning(&format!(
+                "The hooks rewrote lines you also changed without staging in: {}",
+                conflicted.join(", ")
+            ));
+        }
+        for file in &conflicted {
+            // Keeping the unstaged version loses nothing: the rewrite is committed
+            let side = if self.app_config.non_interactive {
+                ConflictSide::Theirs
+            } else {
+                self.ui_service.select_unstaged_conflict_side(file).unwrap_or(ConflictSide::Theirs)
+            };
+            git_service.take_conflict_side(file, side)?;
+            kept_all &= side == ConflictSide::Theirs;
+        }
+
+        if !kept_all {
+            return self.keep_hidden_changes(git_service, stash);
+        }
+        if let Some(index) = git_service.find_stash(stash)? {
+            git_service.stash_drop(&format!("stash@{{{}}}", index))?;
+        }
+        self.ui_service.show_info("Restored unstaged changes");
+        Ok(())
+    }
+
+    fn keep_hidden_changes(&self, git_service: &GitService, stash: &str) -> Result<()> {
+        LeftoverStashes::new(&git_service.get_git_dir()?).add(stash)?;
+        self.ui_service.show_warning("Your unstaged changes are kept in the stash; run `git-cli stash pop` to get them back");
+        Ok(())
+    }
+
+    fn scan_staged(&self) -> Result<()> {
+        self.scan_staged_in(&self.git_service)
+    }
+
+    /// Scans the staged content of `git_service`'s repository for secrets,
+    /// sensitive files and files above `scan.max_file_size_kb`, offering to
+    /// move the latter to Git LFS. Any remaining finding blocks the commit
+    /// unless the user overrides it.
+    fn scan_staged_in(&self, git_service: &GitService) -> Result<()> {
+        if !self.config.scan.enabled {
+            return Ok(());
+        }
+        if self.app_config.no_scan {
+            self.ui_service.show_warning("Skipping the secret and large-file scan (--no-scan)");
+            return Ok(());
+        }
+
+        let root = git_service.get_work_tree_root()?;
+        let allowlist = Allowlist::load(&root.join(&self.config.scan.allowlist_file))?;
+        let scanner = Scanner::new(&self.config.scan, allowlist)?;
+
+        let files: Vec<String> = git_service
+            .get_staged_files()?
+            .into_iter()
+            .filter(|file| !scanner.allows_path(file))
+            .collect();
+        if files.is_empty() {
+            return Ok(());
+        }
+
+        let limit = self.config.scan.max_file_size_kb * 1024;
+        let mut findings = Vec::new();
+        let mut readable = Vec::new();
+        for (path, size) in git_service.get_staged_sizes(&files)? {
+            match size {
+                Some(size) if size > limit => {
+                    let size = scan::format_size(size);
+                    if !self.app_config.non_interactive && git_service.is_lfs_available() && self.ui_service.confirm_lfs_track(&path, &size)? {
+                        git_service.lfs_track(&path)?;
+                        continue;
+                    }
+                    findings.push(Finding {
+                        path,
+                        line: None,
+                        kind: format!("Large file ({}, limit {})", size, scan::format_size(limit)),
+                        excerpt: None,
+                    });
+                }
+                Some(_) => readable.push(path),
+                None => findings.push(unreadable_finding(path)),
+            }
+        }
+
+        // Whatever cannot be read cannot be vouched for either
+        let blobs = git_service.read_staged_blobs(&readable)?;
+        for path in readable.into_iter().filter(|path| !blobs.iter().any(|(scanned, _)| scanned == path)) {
+            findings.push(unreadable_finding(path));
+        }
+        for (path, content) in blobs {
+            findings.extend(scanner.scan(&path, &content));
+        }
+
+        if findings.is_empty() {
+            return Ok(());
+        }
+        self.ui_service.show_scan_findings(&findings);
+        if !self.app_config.non_interactive && self.ui_service.confirm_commit_with_findings(findings.len())? {
+            self.ui_service.show_warning(&format!("Committing despite {} scanner findings", findings.len()));
+            return Ok(());
+        }
+        Err(GitCliError::ScanFindings(findings.len()))
+    }
+
+    /// Runs the `[[fixers]]` on the staged files they match, re-stages what
+    /// they rewrote and summarizes it. A failing fixer stops the commit.
+    fn run_fixers(&self, git_service: &GitService) -> Result<()> {
+        let staged = git_service.get_staged_files()?;
+        let planned = hooks::match_fixers(&self.config.fixers, &staged);
+        if planned.is_empty() {
+            return Ok(());
+        }
+
+        let mut runs = Vec::new();
+        let mut rewritten = BTreeSet::new();
+        for (fixer, files) in planned {
+            self.ui_service.show_info(&format!("Running {} on {} files...", fixer.command, files.len()));
+            let before = git_service.snapshot_dirty_files()?;
+            let (output, success) = git_service.run_shell(&hooks::fixer_command(fixer, &files))?;
+            if !success {
+                return Err(GitCliError::FixerFailed {
+                    command: fixer.command.clone(),
+                    output: output.trim_end().to_string(),
+                });
+            }
+
+            let changed = before.changed_files(&git_service.snapshot_dirty_files()?);
+            rewritten.extend(changed.iter().cloned());
+            runs.push(FixerRun { command: fixer.command.clone(), files: files.len(), changed });
+        }
+
+        let rewritten: Vec<String> = rewritten.into_iter().collect();
+        let diff = if rewritten.is_empty() { String::new() } else { git_service.get_unstaged_diff(&rewritten)? };
+        self.ui_service.show_fixer_summary(&runs, &diff);
+        if !rewritten.is_empty() {
+            self.add_files_in(git_service, &rewritten)?;
+            self.ui_service.show_info(&format!("Re-staged {} files rewritten by the fixers", rewritten.len()));
+        }
+        Ok(())
+    }
+
+    /// Syncs with the upstream and offers to push. Ctrl-C also kills the git
+    /// child of the step it lands in, so it is checked before every step.
+    async fn handle_remote_operations(&self) -> Result<()> {
+        self.check_interrupted()?;
+        let branch = self.git_service.get_current_branch()?;
+        self.ui_service.show_info(&format!("Current branch: {}", branch));
+
+        let upstream = self.git_service.get_upstream(&branch)?;
+        match &upstream {
+            Some(upstream) => self.ui_service.show_info(&format!("Upstream: {}", upstream)),
+            None => self.ui_service.show_info("This branch has no upstream yet"),
+        }
+
+        match &upstream {
+            _ if self.config.sync.pull_strategy == PullStrategy::Never => {
+                self.ui_service.show_info("Skipping pull (sync.pull_strategy = never)");
+            }
+            Some(upstream) => self.sync_with_remote(upstream).await,
+            None => {}
+        }
+        self.check_interrupted()?;
+
+        if self.git_service.get_conflict_state()? != ConflictState::None {
+            self.ui_service.show_warning("Skipping push until the conflicts are resolved. Run `git-cli resolve` to continue.");
+            return Ok(());
+        }
+
+        let (branch, upstream) = if self.is_protected(&branch) {
+            match self.move_off_protected(&branch, upstream.as_ref())? {
+                Some(new_branch) => (new_branch, None),
+                None => return Ok(()),
+            }
+        } else {
+            (branch, upstream)
+        };
+
+        // Show where the branch stands before offering to push. In triangular
+        // workflows the push remote is not the upstream, so being level with
+        // the upstream says nothing about what still needs pushing.
+        if let Some(upstream) = &upstream {
+            let pushes_to_upstream = self.git_service.get_push_remote(&branch)?.as_deref() == Some(upstream.remote.as_str());
+            match self.git_service.get_divergence() {
+                Ok(divergence) if divergence.ahead == 0 && pushes_to_upstream => {
+                    self.ui_service.show_info(&format!("Nothing to push ({})", divergence));
+                    // Mirrors may still lag behind the upstream
+                    if !self.config.sync.mirror_remotes.is_empty() && !self.app_config.non_interactive && self.ui_service.confirm_push()? {
+                        self.push_mirrors(&branch, &upstream.remote, &self.push_options())?;
+                    }
+                    return Ok(());
+                }
+                Ok(divergence) => self.ui_service.show_info(&format!("Branch is {}", divergence)),
+                Err(e) => warn!("Failed to compare with upstream: {}", e),
+            }
+        }
+
+        // Ask user if they want to push
+        if !self.app_config.non_interactive {
+            let should_push = self.ui_service.confirm_push()?;
+            self.check_interrupted()?;
+            if should_push {
+                self.push_branch(&branch, upstream.as_ref(), self.push_options())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_protected(&self, branch: &str) -> bool {
+        branch::is_protected(branch, &self.config.branches.protected)
+    }
+
+    /// Refuses to commit or rewrite history on a protected branch. With
+    /// `carry`, the emoji and title to name a branch after, the user may move
+    /// the index and working tree to a new branch and go on there instead.
+    fn guard_protected_branch(&self, carry: Option<(&str, &str)>) -> Result<()> {
+        let current_branch = self.git_service.get_current_branch()?;
+        if !self.is_protected(&current_branch) {
+            return Ok(());
+        }
+
+        let Some((emoji, title)) = carry.filter(|_| !self.app_config.non_interactive) else {
+            return Err(GitCliError::ProtectedBranch(current_branch));
+        };
+        self.ui_service.show_warning(&format!("Refusing to commit on protected branch {}", current_branch));
+        if self.branch_off(&current_branch, emoji, title)?.is_none() {
+            return Err(GitCliError::ProtectedBranch(current_branch));
+        }
+        Ok(())
+    }
+
+    /// Asks for a branch name (prefilled from `branches.name_template`) and
+    /// switches to it, carrying the index and working tree along. Returns
+    /// `None` if the user chose to stay.
+    fn branch_off(&self, from: &str, emoji: &str, title: &str) -> Result<Option<String>> {
+        if !self.ui_service.confirm_leave_protected_branch(from)? {
+            return Ok(None);
+        }
+
+        let parts = branch::BranchNameParts {
+            commit_type: ValidationService::commit_type_for_emoji(emoji).to_string(),
+            slug: branch::slugify(title),
+            issue: None,
+        };
+        let name = self.prompt_branch_name(branch::render_branch_name(&self.config.branches.name_template, &parts))?;
+
+        self.git_service.create_branch(&name)?;
+        self.record(JournalStep::SwitchBranch { from: from.to_string(), created: name.clone() });
+        self.ui_service.show_success(&format!("Switched to new branch {}", name));
+        Ok(Some(name))
+    }
+
+    /// Explains why `name` cannot be used for a new branch, if it cannot.
+    fn check_new_branch_name(&self, name: &str) -> Result<Option<String>> {
+        let problem = if self.is_protected(name) {
+            Some(format!("{} is a protected branch", name))
+        } else if self.git_service.resolve_ref(&format!("refs/heads/{}", name))?.is_some() {
+            Some(format!("Branch {} already exists", name))
+        } else if !self.git_service.is_valid_branch_name(name)? {
+            Some(format!("{} is not a valid branch name", name))
+        } else {
+            None
+        };
+        Ok(problem)
+    }
+
+    fn prompt_branch_name(&self, mut initial: String) -> Result<String> {
+        loop {
+            let name = self.ui_service.get_branch_name(&initial)?;
+            match self.check_new_branch_name(&name)? {
+                Some(problem) => self.ui_service.show_error(&problem),
+                None => return Ok(name),
+            }
+            initial = name;
+        }
+    }
+
+    /// Creates a branch named from `branches.name_template` and switches to
+    /// it, carrying any uncommitted changes along.
+    fn execute_branch_new(&self, description: Option<&str>, commit_type: Option<&str>, issue: Option<&str>) -> Result<()> {
+        info!("Starting git-cli branch new workflow");
+
+        let template = &self.config.branches.name_template;
+        let commit_type = match commit_type {
+            Some(commit_type) => commit_type.to_string(),
+            None if self.app_config.non_interactive || !template.contains("{type}") => "feat".to_string(),
+            None => self.ui_service.select_branch_type(&emojis::get_emojis()?)?.to_string(),
+        };
+
+        let description = match description {
+            Some(description) => description.to_string(),
+            None if self.app_config.non_interactive => {
+                return Err(GitCliError::ValidationError("A branch description is required in non-interactive mode".to_string()));
+            }
+            None => self.ui_service.get_branch_description()?,
+        };
+
+        let issue = match issue {
+            Some(issue) => Some(issue.trim_start_matches('#').to_string()),
+            None if !self.app_config.non_interactive && template.contains("{issue}") => self.ui_service.get_issue_reference()?,
+            None => None,
+        };
+
+        let parts = branch::BranchNameParts { commit_type, slug: branch::slugify(&description), issue };
+        let name = branch::render_branch_name(template, &parts);
+        let name = if self.app_config.non_interactive {
+            if let Some(problem) = self.check_new_branch_name(&name)? {
+                return Err(GitCliError::ValidationError(problem));
+            }
+            name
+        } else {
+            self.prompt_branch_name(name)?
+        };
+
+        let from = self.git_service.get_current_branch()?;
+        self.git_service.create_branch(&name)?;
+        self.record(JournalStep::SwitchBranch { from, created: name.clone() });
+        self.ui_service.show_success(&format!("Switched to new branch {}", name));
+        Ok(())
+    }
+
+    /// Switches to a local branch picked by fuzzy `query`, offering to stash
+    /// uncommitted changes first.
+    fn execute_branch_switch(&self, query: Option<&str>) -> Result<()> {
+        info!("Starting git-cli branch switch workflow");
+
+        let current = self.git_service.get_current_branch()?;
+        let branches: Vec<String> = self.git_service.get_local_branches()?.into_iter().filter(|b| *b != current).collect();
+
+        let candidates = match query {
+            Some(query) => branch::fuzzy_filter(query, &branches),
+            None => branches.iter().collect(),
+        };
+
+        let target = match candidates.as_slice() {
+            [] => {
+                self.ui_service.show_info("No other branch matches.");
+                return Ok(());
+            }
+            [only] => only.to_string(),
+            [best, ..] if query.is_some_and(|q| *best == q) => best.to_string(),
+            _ if self.app_config.non_interactive => {
+                let names: Vec<&str> = candidates.iter().map(|b| b.as_str()).collect();
+                return Err(GitCliError::ValidationError(format!("Several branches match: {}", names.join(", "))));
+            }
+            _ => self.ui_service.select_branch(&candidates)?,
+        };
+
+        if !self.app_config.non_interactive
+            && self.git_service.has_uncommitted_changes()?
+            && self.ui_service.confirm_stash_before_switch(&target)?
+        {
+            let stash_ref = self.git_service.stash_push(&format!("git-cli: switching from {} to {}", current, target))?;
+            self.record(JournalStep::Stash { stash_ref });
+            self.ui_service.show_info("Stashed uncommitted changes; `git-cli undo` or `git stash pop` brings them back");
+        }
+
+        self.git_service.switch_branch(&target)?;
+        self.record(JournalStep::Switch { from: current, to: target.clone() });
+        self.ui_service.show_success(&format!("Switched to branch {}", target));
+        Ok(())
+    }
+
+    /// Deletes local branches that are merged into the default branch or whose
+    /// upstream was deleted, after the user confirms the list.
+    fn execute_branch_clean(&self) -> Result<()> {
+        info!("Starting git-cli branch clean workflow");
+
+        if let Err(e) = self.git_service.fetch_prune() {
+            warn!("Failed to prune remote branches: {}", e);
+        }
+
+        let current = self.git_service.get_current_branch()?;
+        let default_branch = self.git_service.get_default_branch()?;
+        let keep = |name: &str| name == current || Some(name) == default_branch.as_deref() || self.is_protected(name);
+
+        let mut stale: Vec<branch::StaleBranch> = Vec::new();
+        if let Some(base) = &default_branch {
+            for name in self.git_service.get_merged_branches(base)? {
+                if !keep(&name) {
+                    stale.push(branch::StaleBranch { name, reason: branch::StaleReason::Merged(base.clone()) });
+                }
+            }
+        }
+        for name in self.git_service.get_gone_branches()? {
+            if !keep(&name) && !stale.iter().any(|b| b.name == name) {
+                stale.push(branch::StaleBranch { name, reason: branch::StaleReason::UpstreamGone });
+            }
+        }
+
+        if stale.is_empty() {
+            self.ui_service.show_info("No merged or orphaned branches to clean up.");
+            return Ok(());
+        }
+
+        if self.app_config.non_interactive {
+            self.ui_service.show_info("Branches that can be deleted:");
+            for branch in &stale {
+                println!("  {}", branch);
+            }
+            self.ui_service.show_info("Run `git-cli branch clean` interactively to delete them.");
+            return Ok(());
+        }
+
+        for branch in self.ui_service.select_branches_to_delete(&stale)? {
+            let Some(head) = self.git_service.resolve_ref(&format!("refs/heads/{}", branch.name))? else {
+                continue;
+            };
+            // Branches whose upstream is gone are often squash-merged, which
+            // `branch -d` cannot detect; the user has just confirmed them
+            let force = branch.reason == branch::StaleReason::UpstreamGone;
+            self.git_service.delete_branch(&branch.name, force)?;
+            self.record(JournalStep::DeleteBranch { branch: branch.name.clone(), head });
+            self.ui_service.show_success(&format!("Deleted {}", branch));
+        }
+        Ok(())
+    }
+
+    /// Proposes the next semantic version from the commits since the last
+    /// release tag and creates an annotated tag summarizing them.
+    async fn execute_release(&self, pre: Option<&str>, bump_override: Option<Bump>, sign: bool, check: bool) -> Result<()> {
+        info!("Starting git-cli release workflow");
+
+        let prefix = &self.config.release.tag_prefix;
+        let tags = self.release_tags("HEAD")?;
+
+        if check {
+            return self.check_version_files(tags.last().map(|(version, _)| version));
+        }
+
+        let current = tags.last().map_or(Version { major: 0, minor: 0, patch: 0, pre: None }, |(v, _)| v.clone());
+        // A stable release summarizes everything since the previous stable
+        // one, including what already went out in its pre-releases
+        let since = match pre {
+            Some(_) => tags.last(),
+            None => tags.iter().rev().find(|(version, _)| !version.is_prerelease()),
+        }
+        .map(|(_, tag)| tag.as_str());
+
+        let emojis_object = emojis::get_emojis()?;
+        let commits: Vec<ReleaseCommit> = self
+            .git_service
+            .get_commits_between(since, "HEAD")?
+            .iter()
+            .map(|(hash, message)| ReleaseCommit::classify(hash, message, &emojis_object))
+            .collect();
+
+        if commits.is_empty() {
+            self.ui_service.show_info(&format!("Nothing to release since {}", since.unwrap_or("the start of the history")));
+            return Ok(());
+        }
+
+        let detected = release::overall_bump(&commits);
+        self.ui_service.show_release_plan(since, &commits, detected);
+
+        let bump = match bump_override {
+            Some(bump) => bump,
+            None if detected == Bump::None => {
+                self.ui_service.show_info("No breaking changes, features or fixes since the last release; pass --bump to release anyway");
+                return Ok(());
+            }
+            None => detected,
+        };
+
+        let proposed = current.next(bump, pre);
+        let version = if self.app_config.non_interactive {
+            proposed
+        } else {
+            let mut initial = proposed.to_string();
+            loop {
+                let input = self.ui_service.get_release_version(&initial)?;
+                match Version::parse(input.strip_prefix(prefix.as_str()).unwrap_or(&input), "") {
+                    Some(version) if version > current => break version,
+                    Some(_) => self.ui_service.show_error(&format!("The version must be newer than {}", current)),
+                    None => self.ui_service.show_error(&format!("{} is not a semantic version", input)),
+                }
+                initial = input;
+            }
+        };
+
+        let tag = format!("{}{}", prefix, version);
+        if self.git_service.resolve_ref(&format!("refs/tags/{}", tag))?.is_some() {
+            return Err(GitCliError::ValidationError(format!("Tag {} already exists", tag)));
+        }
+
+        let notes = release::release_notes(&tag, &commits);
+        let bumps = self.read_version_files()?;
+        if self.app_config.dry_run {
+            self.ui_service.show_version_bumps(&bumps, &version.to_string());
+            self.ui_service.show_info(&format!("Dry run: would create tag {}", tag));
+            println!("\n{}", notes);
+            return Ok(());
+        }
+
+        self.ui_service.show_version_bumps(&bumps, &version.to_string());
+        if !self.app_config.non_interactive && !self.ui_service.confirm_create_tag(&tag, &notes)? {
+            self.ui_service.show_info("Release cancelled");
+            return Ok(());
+        }
+
+        if !bumps.is_empty() {
+            self.commit_version_bumps(&tag, &version.to_string(), &bumps).await?;
+        }
+
+        self.git_service.create_tag(&tag, &notes, sign || self.config.release.sign_tags)?;
+        self.record(JournalStep::Tag { name: tag.clone() });
+        self.ui_service.show_success(&format!("Created tag {}", tag));
+
+        if self.app_config.non_interactive {
+            return Ok(());
+        }
+
+        let branch = self.git_service.get_current_branch()?;
+        if let Some(remote) = self.git_service.get_push_remote(&branch)? {
+            if self.ui_service.confirm_push_tag(&tag, &remote)? {
+                self.git_service.push_tag(&remote, &tag)?;
+                if let Some(head) = self.head() {
+                    self.record(JournalStep::Push { remote: remote.clone(), branch: tag.clone(), head });
+                }
+                self.ui_service.show_success(&format!("Pushed {} to {}", tag, remote));
+            }
+        }
+        Ok(())
+    }
+
+    /// Groups the commits in `from..to` into Keep a Changelog sections and
+    /// prints them as Markdown or JSON, or merges them into the
+    /// `[Unreleased]` section of the changelog file.
+    fn execute_changelog(&self, from: Option<&str>, to: &str, json: bool, update: bool, file: Option<&str>) -> Result<()> {
+        info!("Starting git-cli changelog workflow");
+
+        if self.git_service.resolve_ref(to)?.is_none() {
+            return Err(GitCliError::ValidationError(format!("Unknown ref {}", to)));
+        }
+
+        // Default to the last stable release before `to`
+        let to_hash = self.git_service.resolve_ref(&format!("{}^{{commit}}", to))?;
+        let last_release = self
+            .release_tags(to)?
+            .into_iter()
+            .rev()
+            .filter(|(version, _)| !version.is_prerelease())
+            .map(|(_, tag)| tag)
+            .find(|tag| self.git_service.resolve_ref(&format!("{}^{{commit}}", tag)).ok().flatten() != to_hash);
+        let from = from.map(str::to_string).or(last_release);
+
+        let emojis_object = emojis::get_emojis()?;
+        let entries: Vec<changelog::ChangelogEntry> = self
+            .git_service
+            .get_commits_between(from.as_deref(), to)?
+            .iter()
+            .filter_map(|(hash, message)| changelog::ChangelogEntry::from_commit(hash, message, &emojis_object))
+            .collect();
+
+        if json {
+            println!("{}", changelog::render_json(from.as_deref(), to, &entries)?);
+            return Ok(());
+        }
+
+        let issue_url = self.config.changelog.issue_url.clone().or_else(|| {
+            let branch = self.git_service.get_current_branch().ok()?;
+            let remote = self.git_service.get_push_remote(&branch).ok()??;
+            let url = self.git_service.get_config(&format!("remote.{}.url", remote)).ok()??;
+            changelog::issue_url_for_remote(&url)
+        });
+
+        if !update {
+            let title = if to == "HEAD" {
+                "[Unreleased]".to_string()
+            } else {
+                let version = Version::parse(to, &self.config.release.tag_prefix).map_or(to.to_string(), |v| v.to_string());
+                format!("[{}] - {}", version, self.git_service.get_commit_date(to)?)
+            };
+            print!("{}", changelog::render_markdown(&title, &entries, issue_url.as_deref()));
+            return Ok(());
+        }
+
+        // --file is relative to where git-cli runs, the configured path to the repository root
+        let path = match file {
+            Some(file) => std::path::PathBuf::from(file),
+            None => self.git_service.get_work_tree_root()?.join(&self.config.changelog.path),
+        };
+        let existing = if path.exists() {
+            std::fs::read_to_string(&path)?
+        } else {
+            "# Changelog\n\nAll notable changes to this project will be documented in this file.\n".to_string()
+        };
+
+        let updated = changelog::update_unreleased(&existing, &entries, issue_url.as_deref());
+        if updated == existing {
+            self.ui_service.show_info(&format!("{} is already up to date", path.display()));
+        } else if self.app_config.dry_run {
+            self.ui_service.show_info(&format!("Dry run: would update the [Unreleased] section of {}", path.display()));
+            print!("{}", changelog::render_sections(&entries, issue_url.as_deref()));
+        } else {
+            std::fs::write(&path, updated)?;
+            self.ui_service.show_success(&format!("Updated the [Unreleased] section of {}", path.display()));
+        }
+        Ok(())
+    }
+
+    /// Semver release tags reachable from `rev`, oldest version first.
+    fn release_tags(&self, rev: &str) -> Result<Vec<(Version, String)>> {
+        let prefix = &self.config.release.tag_prefix;
+        let mut tags: Vec<(Version, String)> = self
+            .git_service
+            .get_merged_tags(rev)?
+            .into_iter()
+            .filter_map(|tag| Version::parse(&tag, prefix).map(|version| (version, tag)))
+            .collect();
+        tags.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(tags)
+    }
+
+    /// Stashes all changes, or only `paths`, under an emoji-prefixed message.
+    fn execute_stash_save(&self, message: Option<&str>, paths: &[String], include_untracked: bool) -> Result<()> {
+        info!("Starting git-cli stash save workflow");
+
+        let changes = self.git_service.get_status()?;
+        if changes.is_empty() {
+            self.ui_service.show_info("No changes to stash.");
+            return Ok(());
+        }
+
+        let paths = if !paths.is_empty() || self.app_config.non_interactive {
+            paths.iter().map(|path| self.git_service.repo_path(path)).collect::<Result<Vec<_>>>()?
+        } else {
+            self.ui_service.show_changes(&changes);
+            if self.ui_service.confirm_stash_all_files()? {
+                Vec::new()
+            } else {
+                let selected = self.ui_service.select_files_to_stash(&changes)?;
+                if selected.is_empty() {
+                    return Err(GitCliError::NoChanges);
+                }
+                selected
+            }
+        };
+
+        // Picking an untracked file means it should be stashed too
+        let include_untracked = include_untracked
+            || changes.iter().any(|c| c.change_type == "Untracked" && paths.contains(&c.value));
+
+        let emojis_object = emojis::get_emojis()?;
+        let emoji = match &self.app_config.emoji {
+            Some(emoji_str) => message::find_emoji(&emojis_object, emoji_str)
+                .map(|e| e.emoji.clone())
+                .ok_or(GitCliError::InvalidEmoji)?,
+            None if self.app_config.non_interactive => "🚧".to_string(),
+            None => self.ui_service.select_emoji_with_default(&emojis_object, Some("🚧"))?.emoji,
+        };
+
+        let branch = self.git_service.get_current_branch()?;
+        let default_message = format!("Work in progress on {}", branch);
+        let message = match message {
+            Some(message) => message.to_string(),
+            None if self.app_config.non_interactive => default_message,
+            None => self.ui_service.get_stash_message(&default_message)?,
+        };
+
+        let full_message = format!("{} {}", emoji, message);
+        let stash_ref = self.git_service.stash_push_paths(&full_message, &paths, include_untracked)?;
+        self.record(JournalStep::Stash { stash_ref });
+
+        let scope = if paths.is_empty() { "all changes".to_string() } else { format!("{} files", paths.len()) };
+        self.ui_service.show_success(&format!("Stashed {} as stash@{{0}}: {}", scope, full_message));
+        Ok(())
+    }
+
+    fn execute_stash_list(&self) -> Result<()> {
+        let stashes = self.git_service.get_stashes()?;
+        if stashes.is_empty() {
+            self.ui_service.show_info("No stashes.");
+            return Ok(());
+        }
+
+        let leftovers = self.leftover_stashes()?.remaining(&stashes)?;
+        self.ui_service.show_stashes(&stashes, &leftovers);
+        Ok(())
+    }
+
+    fn execute_stash_show(&self, query: Option<&str>) -> Result<()> {
+        let Some(entry) = self.select_stash(query, "show")? else {
+            return Ok(());
+        };
+
+        println!("{}\n", entry);
+        self.ui_service.show_stash_diff(&self.git_service.get_stash_diff(&entry.reference(), true)?);
+        Ok(())
+    }
+
+    /// Applies a stash after previewing what it changes, dropping it when `pop` is set.
+    fn execute_stash_apply(&self, query: Option<&str>, pop: bool) -> Result<()> {
+        let action = if pop { "pop" } else { "apply" };
+        let Some(entry) = self.select_stash(query, action)? else {
+            return Ok(());
+        };
+
+        println!("{}\n", entry);
+        self.ui_service.show_stash_diff(&self.git_service.get_stash_diff(&entry.reference(), false)?);
+
+        self.git_service.stash_apply(&entry.reference(), pop)?;
+        if pop {
+            self.record(JournalStep::StashPop { stash_ref: entry.hash.clone() });
+        }
+        self.ui_service.show_success(&format!("Applied {}", entry.message));
+        Ok(())
+    }
+
+    fn execute_stash_drop(&self, query: Option<&str>) -> Result<()> {
+        let Some(entry) = self.select_stash(query, "drop")? else {
+            return Ok(());
+        };
+
+        if !self.app_config.non_interactive {
+            self.ui_service.show_stash_diff(&self.git_service.get_stash_diff(&entry.reference(), false)?);
+            if !self.ui_service.confirm_drop_stash(&entry)? {
+                self.ui_service.show_info("Stash kept");
+                return Ok(());
+            }
+        }
+
+        self.git_service.stash_drop(&entry.reference())?;
+        let message = match &entry.branch {
+            Some(branch) => format!("On {}: {}", branch, entry.message),
+            None => entry.message.clone(),
+        };
+        self.record(JournalStep::StashDrop { stash_ref: entry.hash.clone(), message });
+        self.ui_service.show_success(&format!("Dropped {}; `git-cli undo` brings it back", entry));
+        Ok(())
+    }
+
+    /// The stash `query` refers to (see `stash::find_stashes`), asking when it
+    /// is ambiguous. Without a query the latest stash is used in
+    /// non-interactive mode and picked from the list otherwise.
+    fn select_stash(&self, query: Option<&str>, action: &str) -> Result<Option<StashEntry>> {
+        let stashes = self.git_service.get_stashes()?;
+        if stashes.is_empty() {
+            self.ui_service.show_info("No stashes.");
+            return Ok(None);
+        }
+
+        let candidates = match query {
+            Some(query) => stash::find_stashes(query, &stashes),
+            None if self.app_config.non_interactive => stashes.iter().take(1).collect(),
+            None => stashes.iter().collect(),
+        };
+
+        let entry = match candidates.as_slice() {
+            [] => {
+                return Err(GitCliError::ValidationError(format!("No stash matches {}", query.unwrap_or_default())));
+            }
+            [only] => (*only).clone(),
+            [best, ..] if query.is_some_and(|q| best.message == q) => (*best).clone(),
+            _ if self.app_config.non_interactive => {
+                let names: Vec<String> = candidates.iter().map(|s| s.reference()).collect();
+                return Err(GitCliError::ValidationError(format!("Several stashes match: {}", names.join(", "))));
+            }
+            _ => self.ui_service.select_stash(&candidates, action)?,
+        };
+        Ok(Some(entry))
+    }
+
+    /// Commits the same change in every repository listed in a workspace file:
+    /// files are picked per repository, the message is asked for once, and
+    /// the pushes run in parallel. Fails when any repository did.
+    async fn execute_workspace(&self, file: Option<&str>) -> Result<()> {
+        info!("Starting git-cli workspace workflow");
+
+        if self.app_config.force {
+            return Err(GitCliError::ValidationError("--force is not supported in workspace mode; use --force-with-lease instead".to_string()));
+        }
+
+        let repos = workspace::load_workspace(std::path::Path::new(file.unwrap_or(workspace::DEFAULT_FILE)))?;
+        let names: Vec<String> = repos.iter().map(|repo| repo.name.clone()).collect();
+        let mut outcomes: Vec<Option<RepoOutcome>> = vec![None; repos.len()];
+        let mut selections: Vec<WorkspaceCommit> = Vec::new();
+
+        for (index, repo) in repos.into_iter().enumerate() {
+            match self.select_workspace_changes(index, repo) {
+                Ok(Ok(selection)) => selections.push(selection),
+                Ok(Err(outcome)) => outcomes[index] = Some(outcome),
+                Err(e @ (GitCliError::InquireError(_) | GitCliError::UserCancelled)) => return Err(e),
+                Err(e) => outcomes[index] = Some(RepoOutcome::Failed(e.to_string())),
+            }
+        }
+
+        if !selections.is_empty() {
+            let all_changes: Vec<git::Change> = selections.iter().flat_map(|s| s.changes.iter().cloned()).collect();
+            let (emoji, title, body) = self.get_commit_details(&all_changes, None).await?;
+            let commit_title = format!("{} {}", emoji.emoji, title);
+
+            let mut committed: Vec<(WorkspaceCommit, String)> = Vec::new();
+            for selection in selections {
+                match self.commit_in_workspace_repo(&selection, &commit_title, body.as_deref()).await {
+                    Ok(hash) => committed.push((selection, hash)),
+                    Err(e) => outcomes[selection.index] = Some(RepoOutcome::Failed(e.to_string())),
+                }
+            }
+
+            let push = !committed.is_empty() && !self.app_config.non_interactive && self.ui_service.confirm_push()?;
+
+            // The signing policy is settled per repository before anything is pushed
+            let mut allowed = Vec::new();
+            for (selection, _) in &committed {
+                allowed.push(push && self.check_workspace_signatures(selection)?);
+            }
+
+            let pushes: Vec<Option<Result<(String, String)>>> = if push {
+                let options = self.push_options();
+                std::thread::scope(|scope| {
+                    let handles: Vec<_> = committed
+                        .iter()
+                        .zip(&allowed)
+                        .map(|((selection, _), allowed)| allowed.then(|| scope.spawn(|| push_workspace_repo(&selection.git_service, &options))))
+                        .collect();
+                    handles
+                        .into_iter()
+                        .map(|handle| {
+                            handle.map(|handle| handle.join().unwrap_or_else(|_| Err(GitCliError::GitCommandFailed("Push thread panicked".to_string()))))
+                        })
+                        .collect()
+                })
+            } else {
+                committed.iter().map(|_| None).collect()
+            };
+
+            for ((selection, hash), push) in committed.into_iter().zip(pushes) {
+                outcomes[selection.index] = Some(match push {
+                    None => RepoOutcome::Committed { hash },
+                    Some(Ok((remote, branch))) => {
+                        if let Some(head) = selection.git_service.resolve_ref("HEAD").ok().flatten() {
+                            let journal = selection.git_service.get_git_dir().map(|dir| Journal::new(&dir));
+                            if let Err(e) = journal.and_then(|j| j.record(JournalStep::Push { remote: remote.clone(), branch: branch.clone(), head })) {
+                                warn!("Failed to record push in {}: {}", selection.repo.name, e);
+                            }
+                        }
+                        RepoOutcome::Pushed { hash, target: format!("{}/{}", remote, branch) }
+                    }
+                    Some(Err(e)) => RepoOutcome::PushFailed { hash, error: e.to_string() },
+                });
+            }
+        }
+
+        let report: Vec<(String, RepoOutcome)> = names
+            .into_iter()
+            .zip(outcomes)
+            .map(|(name, outcome)| (name, outcome.unwrap_or_else(|| RepoOutcome::Skipped("not processed".to_string()))))
+            .collect();
+        self.ui_service.show_workspace_report(&report);
+        if self.app_config.non_interactive && report.iter().any(|(_, outcome)| matches!(outcome, RepoOutcome::Committed { .. })) {
+            self.ui_service.show_info("Nothing was pushed: workspace mode only pushes interactively (--no-interactive)");
+        }
+        if self.app_config.no_verify {
+            self.ui_service.show_warning("Git hooks were skipped for this session (--no-verify)");
+        }
+
+        let failed = report.iter().filter(|(_, outcome)| outcome.is_failure()).count();
+        if failed > 0 {
+            return Err(GitCliError::GitCommandFailed(format!("{} of {} repositories failed", failed, report.len())));
+        }
+        Ok(())
+    }
+
+    /// Shows a workspace repository's changes and lets the user pick the files
+    /// to commit. Repositories that take no part are returned as an outcome.
+    fn select_workspace_changes(&self, index: usize, repo: WorkspaceRepo) -> Result<std::result::Result<WorkspaceCommit, RepoOutcome>> {
+        let git_service = GitService::new(self.app_config.debug)
+            .with_signing(self.config.signing.clone())
+            .with_verify(!self.app_config.no_verify)
+            .with_work_tree(repo.path.clone());
+        git_service.verify_git_initialized()?;
+
+        // A plain directory inside another repository would otherwise commit there
+        let root = git_service.discover_work_tree()?;
+        if root.canonicalize()? != repo.path.canonicalize()? {
+            return Err(GitCliError::ValidationError(format!("not a repository root (inside {})", root.display())));
+        }
+
+        let mut changes = git_service.get_status()?;
+        changes.retain(|change| !change.is_submodule());
+        if changes.is_empty() {
+            return Ok(Err(RepoOutcome::Skipped("no changes".to_string())));
+        }
+
+        let branch = git_service.get_current_branch()?;
+        if self.is_protected(&branch) {
+            return Err(GitCliError::ProtectedBranch(branch));
+        }
+
+        self.ui_service.show_info(&format!("\n{} ({})", repo.name, branch));
+        self.ui_service.show_changes(&changes);
+
+        let files = if self.app_config.non_interactive || self.ui_service.confirm_add_all_files()? {
+            Vec::new()
+        } else {
+            let files = self.ui_service.select_files_to_commit(&changes)?;
+            if files.is_empty() {
+                return Ok(Err(RepoOutcome::Skipped("no files selected".to_string())));
+            }
+            files
+        };
+
+        Ok(Ok(WorkspaceCommit { index, repo, git_service, changes, files }))
+    }
+
+    fn check_workspace_signatures(&self, selection: &WorkspaceCommit) -> Result<bool> {
+        let git_service = &selection.git_service;
+        let upstream = git_service.get_upstream(&git_service.get_current_branch()?)?;
+        let allowed = self.check_outgoing_signatures_in(git_service, upstream.as_ref(), Some(&selection.repo.name))?;
+        if !allowed {
+            self.ui_service.show_info(&format!("Not pushing {}", selection.repo.name));
+        }
+        Ok(allowed)
+    }
+
+    /// Stages the selected files and commits them through the same fixers and
+    /// pre-commit hooks as a regular commit, journaled in the repository's own
+    /// journal so `git-cli -C <repo> undo` can revert it. The index is
+    /// restored when the commit fails.
+    async fn commit_in_workspace_repo(&self, selection: &WorkspaceCommit, title: &str, body: Option<&str>) -> Result<String> {
+        let git_service = &selection.git_service;
+        if let Err(e) = Journal::new(&git_service.get_git_dir()?).start_session("workspace") {
+            warn!("Failed to start journal session in {}: {}", selection.repo.name, e);
+        }
+
+        let index_tree = git_service.write_tree()?;
+        let orig_head = git_service.resolve_ref("HEAD")?;
+
+        let result = async {
+            self.add_files_in(git_service, &selection.files)?;
+            self.scan_staged_in(git_service)?;
+            if !self.attempt_commit_in(git_service, title, body).await? {
+                return Err(GitCliError::GitCommandFailed("Commit failed".to_string()));
+            }
+            Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            if let Err(restore_error) = git_service.read_tree(&index_tree) {
+                warn!("Failed to restore the index in {}: {}", selection.repo.name, restore_error);
+            }
+            return Err(e);
+        }
+
+        if let Some(new_head) = git_service.resolve_ref("HEAD")? {
+            self.record_in(git_service, JournalStep::Commit { orig_head, new_head });
+        }
+        git_service.get_head_hash()
+    }
+
+    /// The configured `release.version_files` with their current versions,
+    /// resolved against the work tree root.
+    fn read_version_files(&self) -> Result<Vec<VersionFileState>> {
+        let root = self.git_service.get_work_tree_root()?;
+        let mut files = Vec::new();
+
+        for file in &self.config.release.version_files {
+            let format = VersionFormat::for_file(file)?;
+            let location = root.join(&file.path);
+            let content = std::fs::read_to_string(&location)
+                .map_err(|e| GitCliError::ConfigError(format!("Failed to read {}: {}", file.path, e)))?;
+            let version = format
+                .read(&content)
+                .map_err(|_| GitCliError::ValidationError(format!("No version found in {}", file.path)))?;
+            files.push(VersionFileState { path: file.path.clone(), location, format, version });
+        }
+        Ok(files)
+    }
+
+    /// Writes `version` into every version file not already at it, refreshes
+    /// their lockfiles and commits the result as the release commit. The
+    /// files and the index are put back as they were when that fails.
+    async fn commit_version_bumps(&self, tag: &str, version: &str, bumps: &[VersionFileState]) -> Result<()> {
+        // Files already at the target need no release commit; the tag goes on HEAD
+        let pending: Vec<&VersionFileState> = bumps.iter().filter(|file| file.version != version).collect();
+        if pending.is_empty() {
+            self.ui_service.show_info(&format!("Version files are already at {}", version));
+            return Ok(());
+        }
+        self.guard_protected_branch(Some(("🔖", &format!("Release {}", tag))))?;
+
+        let mut originals = Vec::new();
+        for file in &pending {
+            originals.push((file.location.clone(), std::fs::read(&file.location)?));
+            if let Some(lockfile) = version_files::lockfile_for(&file.format, &file.location) {
+                let content = std::fs::read(&lockfile)?;
+                originals.push((lockfile, content));
+            }
+        }
+
+        let result = self.with_index_rollback(self.commit_release(tag, version, &pending)).await;
+        if result.is_err() {
+            for (path, content) in &originals {
+                if let Err(e) = std::fs::write(path, content) {
+                    self.ui_service.show_error(&format!("Failed to restore {}: {}", path.display(), e));
+                }
+            }
+        }
+        result
+    }
+
+    /// Writes the bumps and commits them through the fixers and hooks, keeping
+    /// anything else that is staged out of the release commit.
+    async fn commit_release(&self, tag: &str, version: &str, pending: &[&VersionFileState]) -> Result<()> {
+        let root = self.git_service.get_work_tree_root()?;
+        let relative = |path: &std::path::Path| path.strip_prefix(&root).unwrap_or(path).display().to_string();
+
+        let mut changed = Vec::new();
+        for file in pending {
+            let content = std::fs::read_to_string(&file.location)?;
+            std::fs::write(&file.location, file.format.replace(&content, version)?)?;
+            changed.push(relative(&file.location));
+
+            match version_files::refresh_lockfile(&file.format, &file.location) {
+                Ok(Some(lockfile)) => changed.push(relative(&lockfile)),
+                Ok(None) => {}
+                Err(e) => self.ui_service.show_warning(&format!("Could not refresh the lockfile for {}: {}", file.path, e)),
+            }
+        }
+
+        self.add_files(&changed)?;
+        let held_back = self.hold_back_all_but(&changed)?;
+        self.scan_staged()?;
+        let orig_head = self.head();
+        if !self.attempt_commit(&format!("🔖 Release {}", tag), None).await? {
+            return Err(GitCliError::GitCommandFailed("Release commit failed".to_string()));
+        }
+        if let Some(new_head) = self.head() {
+            self.record(JournalStep::Commit { orig_head, new_head });
+        }
+        self.ui_service.show_success(&format!("Committed version bump to {}", version));
+        self.restage_held_back(held_back)
+    }
+
+    /// Reports version files that disagree with each other or with the latest
+    /// release tag, failing so CI can catch the drift.
+    fn check_version_files(&self, released: Option<&Version>) -> Result<()> {
+        let files = self.read_version_files()?;
+        if files.is_empty() {
+            self.ui_service.show_info("No release.version_files configured");
             return Ok(());
         }
 
-        self.ui_service.show_changes(&changes);
+        let expected = released.map(|v| v.to_string()).unwrap_or_else(|| files[0].version.clone());
+        let mut mismatched = 0;
+        for file in &files {
+            if Version::parse(&file.version, "").map(|v| v.to_string()) == Some(expected.clone()) {
+                self.ui_service.show_info(&format!("  {}: {}", file.path, file.version));
+            } else {
+                mismatched += 1;
+                self.ui_service.show_error(&format!("  {}: {} (expected {})", file.path, file.version, expected));
+            }
+        }
 
-        // Stage files
-        let selected_files = self.stage_files(&changes).await?;
+        if mismatched > 0 {
+            return Err(GitCliError::ValidationError(format!("{} version files do not match {}", mismatched, expected)));
+        }
+        self.ui_service.show_success(&format!("All version files are at {}", expected));
+        Ok(())
+    }
 
-        // Get commit details
-        let (emoji, title, body) = self.get_commit_details(&changes).await?;
+    /// Moves commits made on a protected branch to a new branch and resets the
+    /// protected branch to its upstream. Returns the branch to push instead,
+    /// or `None` when there is nothing (or nothing allowed) to push.
+    fn move_off_protected(&self, branch: &str, upstream: Option<&Upstream>) -> Result<Option<String>> {
+        if upstream.is_some() {
+            match self.git_service.get_divergence() {
+                Ok(divergence) if divergence.ahead == 0 => {
+                    self.ui_service.show_info(&format!("Nothing to push ({})", divergence));
+                    return Ok(None);
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Failed to compare with upstream: {}", e),
+            }
+        }
 
-        // Create commit message
-        let commit_title = format!("{} {}", emoji.emoji, title);
-        
-        // Attempt commit
-        let commit_successful = self.attempt_commit(&commit_title, body.as_deref(), &selected_files).await?;
-        
-        if !commit_successful {
-            return Err(GitCliError::GitCommandFailed("Commit failed".to_string()));
+        self.ui_service.show_warning(&format!("Refusing to push to protected branch {}", branch));
+        if self.app_config.non_interactive {
+            return Ok(None);
         }
 
-        self.ui_service.show_success(&format!("Successfully committed with emoji: {}", emoji.emoji));
+        let emojis_object = emojis::get_emojis()?;
+        let head = CommitMessage::parse(&self.git_service.get_head_message()?, &emojis_object);
+        let Some(new_branch) = self.branch_off(branch, head.emoji.as_deref().unwrap_or_default(), &head.title)? else {
+            return Ok(None);
+        };
 
-        // Handle remote operations
-        self.handle_remote_operations().await?;
+        if let Some(upstream) = upstream {
+            // HEAD is on the new branch now, so ask for the protected branch's upstream by name
+            let upstream_ref = format!("{}@{{upstream}}", branch);
+            if let (Some(orig_head), Some(new_head)) = (self.git_service.resolve_ref(&format!("refs/heads/{}", branch))?, self.git_service.resolve_ref(&upstream_ref)?) {
+                self.git_service.move_branch(branch, &new_head)?;
+                self.record(JournalStep::MoveBranch { branch: branch.to_string(), orig_head, new_head });
+                self.ui_service.show_info(&format!("Reset {} to {}", branch, upstream));
+            }
+        }
 
-        // Show recent commits
-        self.show_commit_summary().await?;
+        Ok(Some(new_branch))
+    }
 
-        Ok(())
+    fn check_outgoing_signatures(&self, upstream: Option<&Upstream>) -> Result<bool> {
+        self.check_outgoing_signatures_in(&self.git_service, upstream, None)
     }
 
-    async fn stage_files(&self, changes: &[git::Change]) -> Result<Vec<String>> {
-        let (add_all, selected_files) = if self.app_config.non_interactive {
-            (true, changes.iter().map(|c| c.value.clone()).collect())
-        } else {
-            let add_all = self.ui_service.confirm_add_all_files()?;
-            let files = if add_all {
-                changes.iter().map(|c| c.value.clone()).collect()
-            } else {
-                self.ui_service.select_files_to_commit(changes)?
-            };
-            (add_all, files)
-        };
+    /// When signing is required in `git_service`'s repository (named `repo`
+    /// in workspace mode), warns about outgoing commits that are not
+    /// (validly) signed and asks whether to push them anyway.
+    fn check_outgoing_signatures_in(&self, git_service: &GitService, upstream: Option<&Upstream>, repo: Option<&str>) -> Result<bool> {
+        if !git_service.signing_required()? {
+            return Ok(true);
+        }
 
-        if selected_files.is_empty() {
-            return Err(GitCliError::NoChanges);
+        let unsigned: Vec<_> = git_service
+            .get_outgoing_signatures(upstream)?
+            .into_iter()
+            .filter(|signature| !signature.status.is_signed())
+            .collect();
+        if unsigned.is_empty() {
+            return Ok(true);
         }
 
-        // Stage files
-        if add_all {
-            self.git_service.add_files(&[])?; // Empty slice means add all
-        } else {
-            self.git_service.add_files(&selected_files)?;
+        let location = repo.map(|repo| format!(" in {}", repo)).unwrap_or_default();
+        self.ui_service.show_warning(&format!(
+            "{} outgoing commits{} are not signed, but signing is required:",
+            unsigned.len(),
+            location
+        ));
+        for signature in &unsigned {
+            println!("  {} {} ({})", signature.hash, signature.subject, signature.status);
         }
 
-        self.ui_service.show_info(&format!("Staged {} files", selected_files.len()));
-        Ok(selected_files)
+        if self.app_config.non_interactive {
+            return Ok(false);
+        }
+        self.ui_service.confirm_push_unsigned()
     }
 
-    async fn get_commit_details(&self, changes: &[git::Change]) -> Result<(emojis::Emoji, String, Option<String>)> {
-        let emojis_object = emojis::get_emojis()?;
-
-        let emoji = if let Some(emoji_str) = &self.app_config.emoji {
-            emojis_object
-                .emojis
-                .iter()
-                .find(|e| e.emoji == *emoji_str || e.code == *emoji_str)
-                .cloned()
-                .ok_or(GitCliError::InvalidEmoji)?
+    /// Push settings from the `[push]` config section, overridden by flags.
+    fn push_options(&self) -> PushOptions {
+        let push = &self.config.push;
+        let force = if self.app_config.force {
+            ForceMode::Force
+        } else if self.app_config.force_with_lease {
+            ForceMode::WithLease { expected: None }
         } else {
-            self.ui_service.select_emoji(&emojis_object)?
+            ForceMode::None
         };
 
-        let title = if let Some(title) = &self.app_config.title {
-            title.clone()
-        } else {
-            self.ui_service.get_commit_title(changes)?
+        let mut push_options = push.push_options.clone();
+        push_options.extend(self.app_config.push_options.iter().cloned());
+
+        PushOptions {
+            set_upstream: false,
+            force,
+            follow_tags: push.follow_tags || self.app_config.follow_tags,
+            push_options,
+            dry_run: self.app_config.dry_run,
+        }
+    }
+
+    /// Pushes to the branch's push remote (which may differ from the upstream
+    /// remote in triangular workflows), then to any configured mirrors. A
+    /// branch without an upstream is offered `--set-upstream` on first push,
+    /// and a plain `--force` must be confirmed by typing the branch name.
+    fn push_branch(&self, branch: &str, upstream: Option<&Upstream>, mut options: PushOptions) -> Result<()> {
+        let push_remote = match self.git_service.get_push_remote(branch)? {
+            Some(remote) => remote,
+            None => {
+                self.ui_service.show_error("No remote configured to push to");
+                return Ok(());
+            }
         };
 
-        let body = if let Some(body) = &self.app_config.body {
-            Some(body.clone())
-        } else if !self.app_config.non_interactive {
-            self.ui_service.get_commit_message()?
-        } else {
-            None
+        if options.force == ForceMode::Force {
+            if self.app_config.non_interactive {
+                return Err(GitCliError::ValidationError("--force requires interactive confirmation; use --force-with-lease instead".to_string()));
+            }
+            if !self.ui_service.confirm_force_push(&push_remote, branch)? {
+                return Err(GitCliError::UserCancelled);
+            }
+        }
+
+        if !self.check_outgoing_signatures(upstream)? {
+            self.ui_service.show_info("Push cancelled");
+            return Ok(());
+        }
+
+        options.set_upstream = upstream.is_none() && !options.dry_run && self.ui_service.confirm_set_upstream(&push_remote, branch)?;
+        let remote_branch = match upstream {
+            Some(upstream) if upstream.remote == push_remote => upstream.branch.clone(),
+            _ => branch.to_string(),
         };
 
-        Ok((emoji, title, body))
+        self.push_to(&push_remote, branch, &remote_branch, &options);
+        self.push_mirrors(branch, &push_remote, &options)
     }
 
-    async fn attempt_commit(&self, title: &str, body: Option<&str>, selected_files: &[String]) -> Result<bool> {
-        debug!("Attempting commit with title: {}", title);
-        
-        let success = self.git_service.commit(title, body)?;
-        
-        if !success {
-            warn!("Initial commit failed, checking for pre-commit hooks");
-            
-            if self.config.hooks.retry_on_failure {
-                self.ui_service.show_warning("Pre-commit hook failed. Re-staging files and retrying...");
-                
-                // Re-stage files
-                self.git_service.add_files(selected_files)?;
-                self.ui_service.show_success("Successfully re-staged changes");
-                
-                // Retry commit
-                let retry_success = self.git_service.commit(title, body)?;
-                if !retry_success {
-                    self.ui_service.show_error("Commit failed after retry");
-                    return Ok(false);
+    /// Pushes `branch` to every configured mirror other than `push_remote`.
+    fn push_mirrors(&self, branch: &str, push_remote: &str, options: &PushOptions) -> Result<()> {
+        // Mirrors have their own remote-tracking refs, so any lease is resolved per remote
+        let mirror_options = PushOptions {
+            set_upstream: false,
+            force: match options.force {
+                ForceMode::WithLease { .. } => ForceMode::WithLease { expected: None },
+                ref force => force.clone(),
+            },
+            ..options.clone()
+        };
+        for mirror in &self.config.sync.mirror_remotes {
+            self.check_interrupted()?;
+            if mirror != push_remote {
+                self.push_to(mirror, branch, branch, &mirror_options);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn push_to(&self, remote: &str, branch: &str, remote_branch: &str, options: &PushOptions) {
+        match self.git_service.push(remote, branch, remote_branch, options) {
+            Ok(()) if options.dry_run => {
+                self.ui_service.show_info(&format!("Dry run: would push to {}/{}", remote, remote_branch));
+            }
+            Ok(()) => {
+                if let Some(head) = self.head() {
+                    self.record(JournalStep::Push { remote: remote.to_string(), branch: remote_branch.to_string(), head });
                 }
-                
-                self.ui_service.show_success("Commit successful after retry");
-                return Ok(true);
+                self.ui_service.show_success(&format!("Successfully pushed to {}/{}", remote, remote_branch));
+            }
+            Err(e) => {
+                self.ui_service.show_error(&format!("Failed to push to {}: {}", remote, e));
             }
         }
-        
-        Ok(success)
     }
 
-    async fn handle_remote_operations(&self) -> Result<()> {
+    /// After amend or autosquash rewrote already-pushed commits, offers a
+    /// `--force-with-lease` push pinned to `lease_expected`, the upstream hash
+    /// seen before the rewrite, so nobody else's work is overwritten.
+    fn offer_push_after_rewrite(&self, lease_expected: Option<String>) -> Result<()> {
         let branch = self.git_service.get_current_branch()?;
-        self.ui_service.show_info(&format!("Current branch: {}", branch));
+        let upstream = match self.git_service.get_upstream(&branch)? {
+            Some(upstream) => upstream,
+            None => return Ok(()),
+        };
+
+        let divergence = self.git_service.get_divergence()?;
+        if divergence.ahead == 0 || self.app_config.non_interactive {
+            return Ok(());
+        }
+
+        self.ui_service.show_info(&format!("Compared to {}: {}", upstream, divergence));
+        if !self.ui_service.confirm_push()? {
+            return Ok(());
+        }
+
+        let mut options = self.push_options();
+        if divergence.behind > 0 && options.force == ForceMode::None && self.config.push.force_with_lease_after_rewrite {
+            self.ui_service.show_info("History was rewritten; pushing with --force-with-lease");
+            options.force = ForceMode::WithLease { expected: lease_expected };
+        }
+
+        self.push_branch(&branch, Some(&upstream), options)
+    }
+
+    /// Fetches and, when the remote has new commits, pulls them using the
+    /// configured `[sync]` strategy. Failures are reported but never abort the
+    /// workflow, since the commit has already been made.
+    async fn sync_with_remote(&self, upstream: &Upstream) {
+        let sync = &self.config.sync;
 
         // Fetch remote changes
-        if let Err(e) = self.git_service.fetch_origin(&branch) {
+        if let Err(e) = self.git_service.fetch(upstream) {
             warn!("Failed to fetch from remote: {}", e);
-            return Ok(()); // Continue even if fetch fails
+            return; // Continue even if fetch fails
+        }
+        if utils::is_interrupted() {
+            return;
         }
 
         // Check for remote changes
-        match self.git_service.has_remote_changes(&branch) {
-            Ok(true) => {
-                self.ui_service.show_info("There are changes to pull from the remote repository.");
-                
-                // Try to pull with rebase if configured
-                let use_rebase = true; // Could be made configurable
-                match self.git_service.pull(&branch, use_rebase) {
+        let divergence = match self.git_service.get_divergence() {
+            Ok(divergence) => divergence,
+            Err(e) => {
+                warn!("Failed to check remote changes: {}", e);
+                return;
+            }
+        };
+
+        self.ui_service.show_info(&format!("Compared to {}: {}", upstream, divergence));
+        if divergence.is_diverged() {
+            self.ui_service.show_warning(&format!(
+                "Local and remote histories have diverged ({} local and {} remote commits).",
+                divergence.ahead, divergence.behind
+            ));
+        }
+
+        match divergence.behind {
+            0 => {
+                self.ui_service.show_info("No changes to pull from remote");
+            }
+            _ if !sync.pull_before_push => {
+                self.ui_service.show_warning("There are changes on the remote, but pulling is disabled (sync.pull_before_push = false).");
+            }
+            _ if divergence.is_diverged() && sync.pull_strategy == PullStrategy::FfOnly => {
+                self.ui_service.show_error("Cannot fast-forward diverged histories (sync.pull_strategy = ff-only). Rebase or merge manually before pushing.");
+            }
+            _ => {
+                self.ui_service.show_info(&format!(
+                    "There are changes to pull from the remote repository. Pulling with {}{}...",
+                    sync.pull_strategy,
+                    if sync.autostash { " (autostash)" } else { "" }
+                ));
+
+                let orig_head = self.head();
+                let previous_stash = self.git_service.get_stash_head().ok().flatten();
+                let result = self.git_service.pull(upstream, sync.pull_strategy, sync.autostash);
+
+                // An autostash that could not be re-applied stays on the stash list
+                let leftover_stash = self.git_service.get_stash_head().ok().flatten().filter(|s| Some(s) != previous_stash.as_ref());
+                if let Some(stash_ref) = leftover_stash {
+                    self.record(JournalStep::Stash { stash_ref: stash_ref.clone() });
+                    if let Err(e) = self.leftover_stashes().and_then(|l| l.add(&stash_ref)) {
+                        warn!("Failed to remember leftover stash: {}", e);
+                    }
+                    self.ui_service.show_warning(&format!(
+                        "Your local changes could not be re-applied and were left in stash {} (stash@{{0}}). Run `git stash pop` once the conflicts are resolved.",
+                        &stash_ref[..7.min(stash_ref.len())]
+                    ));
+                }
+
+                match result {
                     Ok(()) => {
-                        self.ui_service.show_success("Successfully pulled changes from remote");
+                        self.record_pull(orig_head);
+                        self.ui_service.show_success(&format!("Successfully pulled changes from remote ({})", sync.pull_strategy));
                     }
-                    Err(_) => {
-                        self.ui_service.show_warning("Pull failed, trying with stash...");
-                        
-                        // Stash, pull, then pop
-                        self.git_service.stash()?;
-                        match self.git_service.pull(&branch, use_rebase) {
-                            Ok(()) => {
-                                self.ui_service.show_success("Successfully pulled changes");
-                                match self.git_service.stash_pop() {
-                                    Ok(()) => self.ui_service.show_success("Successfully restored stashed changes"),
-                                    Err(e) => self.ui_service.show_warning(&format!("Failed to restore stash: {}", e)),
-                                }
-                            }
-                            Err(e) => {
-                                self.ui_service.show_error(&format!("Failed to pull: {}", e));
+                    Err(_) if self.git_service.get_conflict_state().is_ok_and(|state| state != ConflictState::None) => {
+                        self.ui_service.show_error(&format!("Pull with {} stopped with conflicts", sync.pull_strategy));
+                        match self.resolve_conflicts().await {
+                            Ok(true) => {
+                                self.record_pull(orig_head);
+                                self.ui_service.show_success("Pull completed after resolving conflicts");
                             }
+                            Ok(false) => {}
+                            Err(e) => self.ui_service.show_error(&format!("Conflict resolution failed: {}", e)),
+                        }
+                    }
+                    Err(e) => {
+                        self.ui_service.show_error(&format!("Failed to pull with {}: {}", sync.pull_strategy, e));
+                        if !sync.autostash {
+                            self.ui_service.show_info("Local changes are not stashed automatically (sync.autostash = false); commit or stash them and pull again.");
                         }
                     }
                 }
             }
-            Ok(false) => {
-                self.ui_service.show_info("No changes to pull from remote");
+        }
+    }
+
+    async fn execute_resolve(&self) -> Result<()> {
+        info!("Starting git-cli resolve workflow");
+
+        if self.git_service.get_conflict_state()? == ConflictState::None {
+            self.ui_service.show_info("No rebase or merge in progress.");
+            return Ok(());
+        }
+
+        if self.resolve_conflicts().await? {
+            self.ui_service.show_success("All conflicts resolved");
+            self.show_commit_summary().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Walks the user through an interrupted rebase or merge, file by file,
+    /// until it completes, is aborted or is left for later. Returns true once
+    /// the operation has completed.
+    async fn resolve_conflicts(&self) -> Result<bool> {
+        loop {
+            let state = self.git_service.get_conflict_state()?;
+            if state == ConflictState::None {
+                return Ok(true);
             }
-            Err(e) => {
-                warn!("Failed to check remote changes: {}", e);
+
+            let files = self.git_service.get_conflicted_files()?;
+            let (current, pending) = if state == ConflictState::Rebase {
+                (self.git_service.get_current_rebase_commit()?, self.git_service.get_pending_rebase_commits()?)
+            } else {
+                (None, Vec::new())
+            };
+            self.ui_service.show_conflicts(state, &files, current.as_deref(), &pending);
+
+            if self.app_config.non_interactive {
+                self.ui_service.show_info("Resolve the conflicts and run `git-cli resolve`, or abort the operation.");
+                return Ok(false);
             }
-        }
 
-        // Ask user if they want to push
-        if !self.app_config.non_interactive {
-            let should_push = self.ui_service.confirm_push()?;
-            if should_push {
-                match self.git_service.push(&branch) {
-                    Ok(()) => {
-                        self.ui_service.show_success(&format!("Successfully pushed to origin/{}", branch));
+            for file in &files {
+                // A failure leaves this file conflicted without ending the walk
+                loop {
+                    let result = match self.ui_service.select_conflict_action(file, state)? {
+                        ConflictAction::Ours => self.git_service.checkout_conflict_side(file, ConflictSide::Ours),
+                        ConflictAction::Theirs => self.git_service.checkout_conflict_side(file, ConflictSide::Theirs),
+                        ConflictAction::Edit => {
+                            if let Err(e) = self.git_service.open_in_editor(file) {
+                                self.ui_service.show_error(&format!("Could not edit {}: {}", file, e));
+                            }
+                            continue;
+                        }
+                        ConflictAction::MarkResolved => {
+                            if self.git_service.has_conflict_markers(file) {
+                                self.ui_service.show_warning(&format!("{} still contains conflict markers", file));
+                                continue;
+                            }
+                            self.git_service.mark_resolved(file)
+                        }
+                        ConflictAction::Skip => Ok(()),
+                    };
+                    if let Err(e) = result {
+                        self.ui_service.show_error(&format!("Could not resolve {}: {}", file, e));
                     }
-                    Err(e) => {
-                        self.ui_service.show_error(&format!("Failed to push: {}", e));
+                    break;
+                }
+            }
+
+            let remaining = self.git_service.get_conflicted_files()?;
+            match self.ui_service.select_conflict_next_step(remaining.is_empty())? {
+                ConflictNextStep::Continue => {
+                    if !self.git_service.continue_operation(state)? {
+                        self.ui_service.show_warning("The operation stopped again.");
                     }
                 }
+                ConflictNextStep::Resolve => {}
+                ConflictNextStep::Abort => {
+                    self.git_service.abort_operation(state)?;
+                    self.ui_service.show_success("Aborted and restored the previous state");
+                    return Ok(false);
+                }
+                ConflictNextStep::Leave => {
+                    self.ui_service.show_info("Left the operation in progress. Run `git-cli resolve` to pick it up again.");
+                    return Ok(false);
+                }
+            }
+        }
+    }
+
+    fn record_pull(&self, orig_head: Option<String>) {
+        if let (Some(orig_head), Some(new_head)) = (orig_head, self.head()) {
+            if orig_head != new_head {
+                self.record(JournalStep::Pull { orig_head, new_head });
+            }
+        }
+    }
+
+    async fn execute_undo(&self) -> Result<()> {
+        info!("Starting git-cli undo workflow");
+
+        self.git_service.verify_git_initialized()?;
+
+        let journal = self.journal()?;
+        let session = match journal.last_session()? {
+            Some(session) => session,
+            None => {
+                self.ui_service.show_info("Nothing to undo.");
+                return Ok(());
+            }
+        };
+
+        self.ui_service.show_journal_session(&session);
+
+        if let Some(step) = session.steps.iter().find(|step| !step.is_undoable()) {
+            return Err(GitCliError::UndoUnavailable(format!(
+                "the last `{}` session already ran `{}`, which cannot be rolled back",
+                session.command,
+                step.describe()
+            )));
+        }
+
+        if self.git_service.is_rebase_in_progress()? {
+            return Err(GitCliError::UndoUnavailable("a rebase is in progress; finish or abort it first".to_string()));
+        }
+
+        if let Some(expected) = session.final_head() {
+            if self.head().as_deref() != Some(expected) {
+                return Err(GitCliError::UndoUnavailable(format!(
+                    "HEAD has moved since the last `{}` session",
+                    session.command
+                )));
+            }
+        }
+
+        if !self.app_config.non_interactive && !self.ui_service.confirm_undo(&session.command)? {
+            return Err(GitCliError::UserCancelled);
+        }
+
+        for step in session.steps.iter().rev() {
+            self.undo_step(step)?;
+        }
+
+        journal.remove_last_session()?;
+        self.ui_service.show_success(&format!("Undid the last `{}` session", session.command));
+
+        self.show_commit_summary().await?;
+
+        Ok(())
+    }
+
+    fn undo_step(&self, step: &JournalStep) -> Result<()> {
+        debug!("Undoing journal step: {}", step.describe());
+
+        match step {
+            JournalStep::Stage { index_tree } => self.git_service.read_tree(index_tree)?,
+            JournalStep::Commit { orig_head: Some(orig_head), .. } | JournalStep::Amend { orig_head, .. } => {
+                self.git_service.reset_soft(orig_head)?
+            }
+            JournalStep::Commit { orig_head: None, .. } => self.git_service.unborn_head()?,
+            JournalStep::Rebase { orig_head, .. } | JournalStep::Pull { orig_head, .. } => {
+                self.git_service.reset_keep(orig_head)?
+            }
+            JournalStep::Stash { stash_ref } => match self.git_service.find_stash(stash_ref)? {
+                Some(index) => self.git_service.stash_pop_index(index)?,
+                None => debug!("Stash {} was already restored", stash_ref),
+            },
+            // The popped changes are already back in the working tree
+            JournalStep::StashPop { .. } => {}
+            JournalStep::StashDrop { stash_ref, message } => self.git_service.stash_store(stash_ref, message)?,
+            JournalStep::Push { .. } => {
+                return Err(GitCliError::UndoUnavailable(step.describe()));
+            }
+            JournalStep::SwitchBranch { from, created } => {
+                self.git_service.switch_branch(from)?;
+                self.git_service.delete_branch(created, true)?;
             }
+            JournalStep::MoveBranch { branch, orig_head, .. } => self.git_service.move_branch(branch, orig_head)?,
+            JournalStep::Switch { from, .. } => self.git_service.switch_branch(from)?,
+            JournalStep::DeleteBranch { branch, head } => self.git_service.create_branch_at(branch, head)?,
+            JournalStep::Tag { name } => self.git_service.delete_tag(name)?,
         }
 
+        self.ui_service.show_info(&format!("Undone: {}", step.describe()));
         Ok(())
     }
 
@@ -251,26 +2471,80 @@ impl GitWorkflow {
                 warn!("Failed to get recent commits: {}", e);
             }
         }
+
+        match self.git_service.get_signature("HEAD") {
+            Ok(signature) if signature.status.is_signed() || signature.status == SignatureStatus::Bad => {
+                self.ui_service.show_signature(&signature);
+            }
+            // Unsigned commits only deserve a mention when signing is expected
+            Ok(signature) if self.git_service.signing_required().unwrap_or(false) => {
+                self.ui_service.show_signature(&signature);
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Failed to verify the commit signature: {}", e),
+        }
+
+        if self.app_config.no_verify {
+            self.ui_service.show_warning("Git hooks were skipped for this session (--no-verify)");
+        }
         Ok(())
     }
 }
 
+fn unreadable_finding(path: String) -> Finding {
+    Finding { path, line: None, kind: "Staged content could not be read".to_string(), excerpt: None }
+}
+
+/// Pushes the current branch of a workspace repository, setting its upstream
+/// on the first push. Returns the remote and remote branch pushed to.
+fn push_workspace_repo(git_service: &GitService, options: &PushOptions) -> Result<(String, String)> {
+    let branch = git_service.get_current_branch()?;
+    let remote = git_service
+        .get_push_remote(&branch)?
+        .ok_or_else(|| GitCliError::GitCommandFailed("No remote configured to push to".to_string()))?;
+    let upstream = git_service.get_upstream(&branch)?;
+    let remote_branch = match &upstream {
+        Some(upstream) if upstream.remote == remote => upstream.branch.clone(),
+        _ => branch.clone(),
+    };
+
+    let options = PushOptions { set_upstream: upstream.is_none() && !options.dry_run, ..options.clone() };
+    git_service.push(&remote, &branch, &remote_branch, &options)?;
+    Ok((remote, remote_branch))
+}
+
+/// The STASH argument shared by the stash subcommands.
+fn stash_arg() -> Arg {
+    Arg::new("stash")
+        .help("Stash index, stash@{N} or part of its name (defaults to picking one)")
+        .value_name("STASH")
+}
+
 fn parse_args() -> AppConfig {
     let matches = Command::new("Git CLI with Emojis")
-        .version("0.2.0")
+        .version(env!("CARGO_PKG_VERSION"))
         .author("RobertWsp <sousarobert854@gmail.com>")
         .about("A powerful CLI tool for Git commits with emoji support")
+        .arg(
+            Arg::new("directory")
+                .short('C')
+                .help("Run as if git-cli was started in <PATH>")
+                .value_name("PATH")
+                .global(true)
+        )
         .arg(
             Arg::new("debug")
                 .short('d')
                 .long("debug")
                 .help("Enable debug mode")
+                .global(true)
                 .action(clap::ArgAction::SetTrue)
         )
         .arg(
             Arg::new("non-interactive")
                 .long("no-interactive")
                 .help("Run in non-interactive mode")
+                .global(true)
                 .action(clap::ArgAction::SetTrue)
         )
         .arg(
@@ -278,27 +2552,348 @@ fn parse_args() -> AppConfig {
                 .long("emoji")
                 .help("Emoji to use for commit")
                 .value_name("EMOJI")
+                .global(true)
         )
         .arg(
             Arg::new("title")
                 .long("title")
                 .help("Commit title")
                 .value_name("TITLE")
+                .global(true)
         )
         .arg(
             Arg::new("body")
                 .long("body")
                 .help("Commit body/description")
                 .value_name("BODY")
+                .global(true)
+        )
+        .arg(
+            Arg::new("force-with-lease")
+                .long("force-with-lease")
+                .help("Force push, but only if the remote branch is where we last saw it")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("force")
+                .long("force")
+                .help("Force push unconditionally (asks for typed confirmation)")
+                .conflicts_with("force-with-lease")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("follow-tags")
+                .long("follow-tags")
+                .help("Also push annotated tags pointing at pushed commits")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("push-option")
+                .short('o')
+                .long("push-option")
+                .help("Transmit a push option to the server (repeatable), e.g. ci.skip")
+                .value_name("OPTION")
+                .global(true)
+                .action(clap::ArgAction::Append)
+        )
+        .arg(
+            Arg::new("no-verify")
+                .long("no-verify")
+                .help("Skip the pre-commit and commit-msg hooks and the fixers")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("no-scan")
+                .long("no-scan")
+                .help("Commit without scanning the staged files for secrets and large files")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .help("Show what would be pushed or released without doing it")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+        )
+        .subcommand(
+            Command::new("amend")
+                .about("Edit the last commit with the emoji workflow")
+        )
+        .subcommand(
+            Command::new("fixup")
+                .about("Create a fixup! or squash! commit for an outgoing commit")
+                .arg(
+                    Arg::new("target")
+                        .long("target")
+                        .help("Commit to fix up")
+                        .value_name("COMMIT")
+                )
+                .arg(
+                    Arg::new("squash")
+                        .long("squash")
+                        .help("Create a squash! commit instead of fixup!")
+                        .action(clap::ArgAction::SetTrue)
+                )
+        )
+        .subcommand(
+            Command::new("autosquash")
+                .about("Fold fixup! and squash! commits into their targets")
+        )
+        .subcommand(
+            Command::new("undo")
+                .about("Roll back the last git-cli session")
+        )
+        .subcommand(
+            Command::new("resolve")
+                .about("Resolve conflicts left by an interrupted pull, rebase or merge")
+        )
+        .subcommand(
+            Command::new("branch")
+                .about("Create, switch and clean up branches")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("new")
+                        .about("Create a branch named from branches.name_template")
+                        .arg(
+                            Arg::new("description")
+                                .help("Short description used for {slug}")
+                                .value_name("DESCRIPTION")
+                        )
+                        .arg(
+                            Arg::new("type")
+                                .long("type")
+                                .help("Conventional type used for {type}, e.g. feat or fix")
+                                .value_name("TYPE")
+                        )
+                        .arg(
+                            Arg::new("issue")
+                                .long("issue")
+                                .help("Issue reference used for {issue}")
+                                .value_name("ISSUE")
+                        )
+                )
+                .subcommand(
+                    Command::new("switch")
+                        .about("Switch to a local branch, matching QUERY fuzzily")
+                        .arg(
+                            Arg::new("query")
+                                .help("Part of the branch name")
+                                .value_name("QUERY")
+                        )
+                )
+                .subcommand(
+                    Command::new("clean")
+                        .about("Delete branches merged into the default branch or whose upstream is gone")
+                )
+        )
+        .subcommand(
+            Command::new("stash")
+                .about("Save, inspect and restore stashes")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("save")
+                        .about("Stash changes under an emoji-prefixed name, optionally only some files")
+                        .arg(
+                            Arg::new("message")
+                                .help("Name of the stash")
+                                .value_name("MESSAGE")
+                        )
+                        .arg(
+                            Arg::new("paths")
+                                .help("Only stash these files")
+                                .value_name("PATHS")
+                                .last(true)
+                                .num_args(1..)
+                        )
+                        .arg(
+                            Arg::new("include-untracked")
+                                .short('u')
+                                .long("include-untracked")
+                                .help("Also stash untracked files")
+                                .action(clap::ArgAction::SetTrue)
+                        )
+                )
+                .subcommand(
+                    Command::new("list")
+                        .about("List stashes, flagging those left behind by a failed pull")
+                )
+                .subcommand(
+                    Command::new("show")
+                        .about("Show the diff of a stash")
+                        .arg(stash_arg())
+                )
+                .subcommand(
+                    Command::new("apply")
+                        .about("Apply a stash and keep it")
+                        .arg(stash_arg())
+                )
+                .subcommand(
+                    Command::new("pop")
+                        .about("Apply a stash and remove it")
+                        .arg(stash_arg())
+                )
+                .subcommand(
+                    Command::new("drop")
+                        .about("Delete a stash")
+                        .arg(stash_arg())
+                )
+        )
+        .subcommand(
+            Command::new("workspace")
+                .about("Commit the same change in every repository listed in a workspace file")
+                .arg(
+                    Arg::new("file")
+                        .long("file")
+                        .help("Workspace file listing one repository path per line (defaults to .git-cli-workspace)")
+                        .value_name("PATH")
+                )
+        )
+        .subcommand(
+            Command::new("changelog")
+                .about("Generate a Keep a Changelog section from the commits between two refs")
+                .arg(
+                    Arg::new("from")
+                        .help("Start of the range (defaults to the last release tag)")
+                        .value_name("FROM")
+                )
+                .arg(
+                    Arg::new("to")
+                        .help("End of the range")
+                        .value_name("TO")
+                        .default_value("HEAD")
+                )
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .help("Print the entries as JSON instead of Markdown")
+                        .conflicts_with("update")
+                        .action(clap::ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("update")
+                        .long("update")
+                        .help("Merge the entries into the [Unreleased] section of the changelog file")
+                        .action(clap::ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("file")
+                        .long("file")
+                        .help("Changelog file to update (defaults to changelog.path)")
+                        .value_name("PATH")
+                        .requires("update")
+                )
+        )
+        .subcommand(
+            Command::new("release")
+                .about("Tag the next semantic version based on the commits since the last release")
+                .arg(
+                    Arg::new("pre")
+                        .long("pre")
+                        .help("Create a pre-release with this identifier, e.g. rc or beta")
+                        .value_name("ID")
+                )
+                .arg(
+                    Arg::new("bump")
+                        .long("bump")
+                        .help("Override the detected release impact")
+                        .value_name("LEVEL")
+                        .value_parser(["major", "minor", "patch"])
+                )
+                .arg(
+                    Arg::new("sign")
+                        .long("sign")
+                        .help("Sign the release tag")
+                        .action(clap::ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("check")
+                        .long("check")
+                        .help("Only report version files that do not match the latest release")
+                        .action(clap::ArgAction::SetTrue)
+                )
         )
         .get_matches();
 
+    let command = match matches.subcommand() {
+        Some(("amend", _)) => CliCommand::Amend,
+        Some(("fixup", sub_matches)) => CliCommand::Fixup {
+            target: sub_matches.get_one::<String>("target").cloned(),
+            squash: sub_matches.get_flag("squash"),
+        },
+        Some(("autosquash", _)) => CliCommand::Autosquash,
+        Some(("undo", _)) => CliCommand::Undo,
+        Some(("resolve", _)) => CliCommand::Resolve,
+        Some(("branch", sub_matches)) => CliCommand::Branch(match sub_matches.subcommand() {
+            Some(("new", new_matches)) => BranchCommand::New {
+                description: new_matches.get_one::<String>("description").cloned(),
+                commit_type: new_matches.get_one::<String>("type").cloned(),
+                issue: new_matches.get_one::<String>("issue").cloned(),
+            },
+            Some(("switch", switch_matches)) => BranchCommand::Switch {
+                query: switch_matches.get_one::<String>("query").cloned(),
+            },
+            _ => BranchCommand::Clean,
+        }),
+        Some(("stash", sub_matches)) => {
+            let stash = |matches: &clap::ArgMatches| matches.get_one::<String>("stash").cloned();
+            CliCommand::Stash(match sub_matches.subcommand() {
+                Some(("save", save_matches)) => StashCommand::Save {
+                    message: save_matches.get_one::<String>("message").cloned(),
+                    paths: save_matches
+                        .get_many::<String>("paths")
+                        .map(|values| values.cloned().collect())
+                        .unwrap_or_default(),
+                    include_untracked: save_matches.get_flag("include-untracked"),
+                },
+                Some(("show", show_matches)) => StashCommand::Show { stash: stash(show_matches) },
+                Some(("apply", apply_matches)) => StashCommand::Apply { stash: stash(apply_matches) },
+                Some(("pop", pop_matches)) => StashCommand::Pop { stash: stash(pop_matches) },
+                Some(("drop", drop_matches)) => StashCommand::Drop { stash: stash(drop_matches) },
+                _ => StashCommand::List,
+            })
+        }
+        Some(("workspace", sub_matches)) => CliCommand::Workspace {
+            file: sub_matches.get_one::<String>("file").cloned(),
+        },
+        Some(("changelog", sub_matches)) => CliCommand::Changelog {
+            from: sub_matches.get_one::<String>("from").cloned(),
+            to: sub_matches.get_one::<String>("to").cloned().unwrap_or_else(|| "HEAD".to_string()),
+            json: sub_matches.get_flag("json"),
+            update: sub_matches.get_flag("update"),
+            file: sub_matches.get_one::<String>("file").cloned(),
+        },
+        Some(("release", sub_matches)) => CliCommand::Release {
+            pre: sub_matches.get_one::<String>("pre").cloned(),
+            bump: sub_matches.get_one::<String>("bump").and_then(|bump| Bump::parse(bump)),
+            sign: sub_matches.get_flag("sign"),
+            check: sub_matches.get_flag("check"),
+        },
+        _ => CliCommand::Commit,
+    };
+
     AppConfig {
+        command,
         debug: matches.get_flag("debug"),
         non_interactive: matches.get_flag("non-interactive"),
         emoji: matches.get_one::<String>("emoji").cloned(),
         title: matches.get_one::<String>("title").cloned(),
         body: matches.get_one::<String>("body").cloned(),
+        force_with_lease: matches.get_flag("force-with-lease"),
+        force: matches.get_flag("force"),
+        follow_tags: matches.get_flag("follow-tags"),
+        push_options: matches
+            .get_many::<String>("push-option")
+            .map(|values| values.cloned().collect())
+            .unwrap_or_default(),
+        dry_run: matches.get_flag("dry-run"),
+        no_verify: matches.get_flag("no-verify"),
+        no_scan: matches.get_flag("no-scan"),
+        directory: matches.get_one::<String>("directory").cloned(),
     }
 }
 
@@ -312,10 +2907,21 @@ async fn main() {
     info!("Git CLI started");
     debug!("App config: {:?}", app_config);
 
+    // Like `git -C`, paths on the command line are relative to the new directory
+    if let Some(directory) = &app_config.directory {
+        if let Err(e) = std::env::set_current_dir(directory) {
+            eprintln!("{}", utils::format_error_message(&format!("Cannot change to {}: {}", directory, e)));
+            std::process::exit(1);
+        }
+    }
+
+    // Ctrl-C cancels the workflow so staged changes can be rolled back
+    utils::spawn_interrupt_listener();
+
     // Create and execute workflow
     match GitWorkflow::new(app_config) {
         Ok(workflow) => {
-            if let Err(e) = workflow.execute().await {
+            if let Err(e) = workflow.run().await {
                 error!("Workflow failed: {}", e);
                 eprintln!("{}", utils::format_error_message(&format!("Error: {}", e)));
                 std::process::exit(1);
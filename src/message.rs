@@ -0,0 +1,88 @@
+use crate::emojis::{Emoji, EmojisObject};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitMessage {
+    pub emoji: Option<String>,
+    pub title: String,
+    pub body: Option<String>,
+}
+
+impl CommitMessage {
+    /// Splits a raw commit message (as printed by `git log --format=%B`) into
+    /// its leading emoji, title and body.
+    pub fn parse(raw: &str, emojis_object: &EmojisObject) -> Self {
+        let raw = raw.trim();
+        let (subject, rest) = match raw.split_once('\n') {
+            Some((subject, rest)) => (subject.trim(), rest),
+            None => (raw, ""),
+        };
+
+        let (emoji, title) = match subject.split_once(' ') {
+            Some((first, title)) => match find_emoji(emojis_object, first) {
+                Some(emoji) => (Some(emoji.emoji.clone()), title.trim().to_string()),
+                None => (None, subject.to_string()),
+            },
+            None => (None, subject.to_string()),
+        };
+
+        let body = rest.trim();
+        let body = if body.is_empty() { None } else { Some(body.to_string()) };
+
+        Self { emoji, title, body }
+    }
+
+    pub fn subject(&self) -> String {
+        match &self.emoji {
+            Some(emoji) => format!("{} {}", emoji, self.title),
+            None => self.title.clone(),
+        }
+    }
+}
+
+/// The `type(scope)!: description` header of a Conventional Commit title.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConventionalHeader<'a> {
+    pub commit_type: &'a str,
+    pub scope: Option<&'a str>,
+    pub breaking: bool,
+    pub description: &'a str,
+}
+
+impl<'a> ConventionalHeader<'a> {
+    pub fn parse(title: &'a str) -> Option<Self> {
+        let (header, description) = title.split_once(':')?;
+        let (header, breaking) = match header.strip_suffix('!') {
+            Some(header) => (header, true),
+            None => (header, false),
+        };
+        let (commit_type, scope) = match header.split_once('(') {
+            Some((commit_type, scope)) => (commit_type, Some(scope.strip_suffix(')')?)),
+            None => (header, None),
+        };
+
+        if commit_type.is_empty() || !commit_type.chars().all(|c| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        Some(Self { commit_type, scope, breaking, description: description.trim() })
+    }
+}
+
+impl CommitMessage {
+    /// 💥, a `type!:` header or a `BREAKING CHANGE` footer mark a breaking change.
+    pub fn is_breaking(&self) -> bool {
+        let body = self.body.as_deref().unwrap_or_default();
+        self.emoji.as_deref().map(|e| e.trim_end_matches('\u{fe0f}')) == Some("💥")
+            || ConventionalHeader::parse(&self.title).is_some_and(|header| header.breaking)
+            || body.lines().any(|line| line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:"))
+    }
+}
+
+/// Finds an emoji by its glyph or `:code:`, ignoring variation selectors so
+/// that `⚡` and `⚡️` are treated as the same emoji.
+pub fn find_emoji<'a>(emojis_object: &'a EmojisObject, value: &str) -> Option<&'a Emoji> {
+    let value = value.trim_end_matches('\u{fe0f}');
+    emojis_object
+        .emojis
+        .iter()
+        .find(|e| e.emoji.trim_end_matches('\u{fe0f}') == value || e.code == value)
+}
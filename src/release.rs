@@ -0,0 +1,215 @@
+use crate::emojis::EmojisObject;
+use crate::message::{CommitMessage, ConventionalHeader};
+use std::cmp::Ordering;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Option<String>,
+}
+
+impl Version {
+    /// Parses `1.2.3` or `1.2.3-rc.1`, after stripping `prefix` (e.g. `v`).
+    /// Build metadata (`+...`) is ignored.
+    pub fn parse(text: &str, prefix: &str) -> Option<Self> {
+        let text = text.trim().strip_prefix(prefix)?;
+        let text = text.split_once('+').map_or(text, |(version, _)| version);
+        let (core, pre) = match text.split_once('-') {
+            Some((core, pre)) if !pre.is_empty() => (core, Some(pre.to_string())),
+            Some(_) => return None,
+            None => (text, None),
+        };
+
+        let mut numbers = core.split('.').map(|n| n.parse::<u64>().ok());
+        let (major, minor, patch) = (numbers.next()??, numbers.next()??, numbers.next()??);
+        if numbers.next().is_some() {
+            return None;
+        }
+
+        Some(Self { major, minor, patch, pre })
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some()
+    }
+
+    /// The version after applying `bump`, following semver precedence: a
+    /// pre-release of the target version is promoted instead of bumped again.
+    /// With `pre_id`, the result is the next `<pre_id>.N` pre-release.
+    pub fn next(&self, bump: Bump, pre_id: Option<&str>) -> Self {
+        let stable = Self { pre: None, ..self.clone() };
+        let promoted = match bump {
+            _ if !self.is_prerelease() => None,
+            Bump::Major if self.minor == 0 && self.patch == 0 => Some(stable.clone()),
+            Bump::Minor if self.patch == 0 => Some(stable.clone()),
+            Bump::Patch | Bump::None => Some(stable.clone()),
+            _ => None,
+        };
+
+        let base = promoted.unwrap_or_else(|| match bump {
+            Bump::Major => Self { major: self.major + 1, minor: 0, patch: 0, pre: None },
+            Bump::Minor => Self { minor: self.minor + 1, patch: 0, ..stable.clone() },
+            Bump::Patch | Bump::None => Self { patch: self.patch + 1, ..stable.clone() },
+        });
+
+        let Some(pre_id) = pre_id else {
+            return base;
+        };
+
+        // Continue the current pre-release series when it targets the same version
+        let number = match &self.pre {
+            Some(pre) if base == stable => pre
+                .strip_prefix(pre_id)
+                .and_then(|rest| rest.strip_prefix('.'))
+                .and_then(|n| n.parse::<u64>().ok())
+                .map_or(1, |n| n + 1),
+            _ => 1,
+        };
+
+        Self { pre: Some(format!("{}.{}", pre_id, number)), ..base }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre) = &self.pre {
+            write!(f, "-{}", pre)?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => compare_pre(a, b),
+            })
+    }
+}
+
+/// Compares dot-separated pre-release identifiers, numerically where both are numbers.
+fn compare_pre(a: &str, b: &str) -> Ordering {
+    let mut a_parts = a.split('.');
+    let mut b_parts = b.split('.');
+    loop {
+        match (a_parts.next(), b_parts.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => {
+                let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+                    (Ok(a), Ok(b)) => a.cmp(&b),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => a.cmp(b),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+/// Release impact of a change, from least to most significant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Bump {
+    None,
+    Patch,
+    Minor,
+    Major,
+}
+
+impl Bump {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "major" => Some(Bump::Major),
+            "minor" => Some(Bump::Minor),
+            "patch" => Some(Bump::Patch),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bump::None => write!(f, "none"),
+            Bump::Patch => write!(f, "patch"),
+            Bump::Minor => write!(f, "minor"),
+            Bump::Major => write!(f, "major"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReleaseCommit {
+    pub hash: String,
+    pub subject: String,
+    pub bump: Bump,
+}
+
+impl ReleaseCommit {
+    /// Classifies a commit by its gitmoji and/or Conventional Commit header:
+    /// breaking changes are major, ✨/`feat` is minor and 🐛/🚑️/`fix` is patch.
+    pub fn classify(hash: &str, raw_message: &str, emojis_object: &EmojisObject) -> Self {
+        let message = CommitMessage::parse(raw_message, emojis_object);
+        let emoji = message.emoji.as_deref().map(|e| e.trim_end_matches('\u{fe0f}'));
+        let commit_type = ConventionalHeader::parse(&message.title).map(|header| header.commit_type);
+
+        let bump = if message.is_breaking() {
+            Bump::Major
+        } else if emoji == Some("✨") || commit_type == Some("feat") {
+            Bump::Minor
+        } else if matches!(emoji, Some("🐛" | "🚑")) || commit_type == Some("fix") {
+            Bump::Patch
+        } else {
+            Bump::None
+        };
+
+        Self { hash: hash.to_string(), subject: message.subject(), bump }
+    }
+}
+
+/// The highest impact among `commits`.
+pub fn overall_bump(commits: &[ReleaseCommit]) -> Bump {
+    commits.iter().map(|c| c.bump).max().unwrap_or(Bump::None)
+}
+
+/// Annotated tag message: the version followed by the commits grouped by impact.
+pub fn release_notes(tag: &str, commits: &[ReleaseCommit]) -> String {
+    let mut notes = format!("Release {}\n", tag);
+    let sections = [
+        (Bump::Major, "Breaking changes"),
+        (Bump::Minor, "Features"),
+        (Bump::Patch, "Fixes"),
+        (Bump::None, "Other changes"),
+    ];
+
+    for (bump, heading) in sections {
+        let entries: Vec<&ReleaseCommit> = commits.iter().filter(|c| c.bump == bump).collect();
+        if entries.is_empty() {
+            continue;
+        }
+        notes.push_str(&format!("\n{}:\n", heading));
+        for commit in entries {
+            notes.push_str(&format!("- {} ({})\n", commit.subject, &commit.hash[..commit.hash.len().min(7)]));
+        }
+    }
+    notes
+}
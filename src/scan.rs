@@ -0,0 +1,239 @@
+use crate::config::ScanConfig;
+use crate::errors::{GitCliError, Result};
+use crate::utils;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+/// Built-in detectors: a name and the pattern of what they look for.
+const DETECTORS: &[(&str, &str)] = &[
+    ("AWS access key", r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b"),
+    ("AWS secret key", r#"(?i)aws_?secret_?access_?key["']?\s*[:=]\s*["']?[A-Za-z0-9/+=]{40}"#),
+    ("Private key", r"-----BEGIN (?:[A-Z]+ )?PRIVATE KEY(?: BLOCK)?-----"),
+    ("GitHub token", r"\b(?:ghp|gho|ghu|ghs|ghr)_[A-Za-z0-9]{36}\b"),
+    ("GitHub token", r"\bgithub_pat_[A-Za-z0-9_]{82}\b"),
+];
+
+/// File names that hold credentials whatever their content.
+const SENSITIVE_FILES: &[&str] = &[".env", ".env.*", "*.pem", "*.key", "*.p12", "*.pfx", "id_rsa", "id_dsa", "id_ecdsa", "id_ed25519"];
+
+/// Templates that look like sensitive files but are meant to be committed.
+const TEMPLATE_SUFFIXES: &[&str] = &[".example", ".sample", ".template", ".dist"];
+
+/// Lockfiles are full of checksums, which look like random strings.
+const ENTROPY_EXEMPT: &[&str] = &["*.lock", "*-lock.json", "*-lock.yaml", "*.sum"];
+
+/// Tokens shorter than this are not checked for entropy.
+const MIN_ENTROPY_TOKEN: usize = 20;
+
+/// Something the scanner would keep out of a commit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub path: String,
+    /// 1-based line of the match; None for findings about the whole file.
+    pub line: Option<usize>,
+    pub kind: String,
+    /// The start of the matched text, with the rest redacted.
+    pub excerpt: Option<String>,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.path, line, self.kind)?,
+            None => write!(f, "{}: {}", self.path, self.kind)?,
+        }
+        if let Some(excerpt) = &self.excerpt {
+            write!(f, " ({})", excerpt)?;
+        }
+        Ok(())
+    }
+}
+
+/// Paths and values the scanner must not report, read from the allowlist
+/// file: one path glob per line, or `value:<text>` to allow one matched
+/// string (such as a documented example key) wherever it appears.
+#[derive(Debug, Clone, Default)]
+pub struct Allowlist {
+    paths: Vec<String>,
+    values: Vec<String>,
+}
+
+impl Allowlist {
+    pub fn parse(content: &str) -> Self {
+        let mut allowlist = Self::default();
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.strip_prefix("value:") {
+                Some(value) => allowlist.values.push(value.trim().to_string()),
+                None => allowlist.paths.push(line.to_string()),
+            }
+        }
+        allowlist
+    }
+
+    /// Reads the allowlist file; a missing file allows nothing.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(Self::parse(&std::fs::read_to_string(path)?))
+    }
+
+    pub fn allows_path(&self, path: &str) -> bool {
+        self.paths.iter().any(|glob| utils::glob_match(glob, path))
+    }
+
+    fn allows_value(&self, value: &str) -> bool {
+        self.values.iter().any(|allowed| value.contains(allowed.as_str()))
+    }
+}
+
+pub struct Scanner {
+    detectors: Vec<(String, Regex)>,
+    entropy_threshold: f64,
+    allowlist: Allowlist,
+}
+
+impl Scanner {
+    /// The built-in detectors plus the configured `[[scan.patterns]]`.
+    pub fn new(config: &ScanConfig, allowlist: Allowlist) -> Result<Self> {
+        let mut detectors = Vec::new();
+        for (name, pattern) in DETECTORS {
+            detectors.push((name.to_string(), Regex::new(pattern).expect("built-in detector pattern")));
+        }
+        for pattern in &config.patterns {
+            let regex = Regex::new(&pattern.regex)
+                .map_err(|e| GitCliError::ConfigError(format!("Invalid scan pattern {}: {}", pattern.name, e)))?;
+            detectors.push((pattern.name.clone(), regex));
+        }
+
+        Ok(Self {
+            detectors,
+            entropy_threshold: config.entropy_threshold,
+            allowlist,
+        })
+    }
+
+    pub fn allows_path(&self, path: &str) -> bool {
+        self.allowlist.allows_path(path)
+    }
+
+    /// Scans the staged content of `path`. Binary content is only checked by name.
+    pub fn scan(&self, path: &str, content: &[u8]) -> Vec<Finding> {
+        if self.allows_path(path) {
+            return Vec::new();
+        }
+
+        let mut findings = Vec::new();
+        if is_sensitive_file(path) {
+            findings.push(Finding {
+                path: path.to_string(),
+                line: None,
+                kind: "Sensitive file".to_string(),
+                excerpt: None,
+            });
+        }
+
+        // Git's own heuristic: a NUL byte early on means binary
+        if content.iter().take(8000).any(|&b| b == 0) {
+            return findings;
+        }
+
+        let text = String::from_utf8_lossy(content);
+        let check_entropy = self.entropy_threshold > 0.0 && !ENTROPY_EXEMPT.iter().any(|glob| matches_file_name(glob, path));
+        for (index, line) in text.lines().enumerate() {
+            let mut matches: Vec<(&str, &str)> = self
+                .detectors
+                .iter()
+                .filter_map(|(name, regex)| regex.find(line).map(|m| (name.as_str(), m.as_str())))
+                .collect();
+
+            // The entropy check only covers what the detectors did not name
+            if matches.is_empty() && check_entropy {
+                let random = line
+                    .split(|c: char| !is_token_char(c))
+                    .filter(|token| token.len() >= MIN_ENTROPY_TOKEN)
+                    .find(|token| looks_random(token) && entropy(token) >= self.entropy_threshold);
+                matches.extend(random.map(|token| ("High-entropy string", token)));
+            }
+
+            for (kind, matched) in matches {
+                if self.allowlist.allows_value(matched) {
+                    continue;
+                }
+                findings.push(Finding {
+                    path: path.to_string(),
+                    line: Some(index + 1),
+                    kind: kind.to_string(),
+                    excerpt: Some(redact(matched)),
+                });
+            }
+        }
+        findings
+    }
+}
+
+/// Shannon entropy of `text` in bits per character.
+pub fn entropy(text: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in text.chars() {
+        *counts.entry(c).or_default() += 1;
+    }
+
+    let length = text.chars().count() as f64;
+    counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / length;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+pub fn is_sensitive_file(path: &str) -> bool {
+    if TEMPLATE_SUFFIXES.iter().any(|suffix| path.ends_with(suffix)) {
+        return false;
+    }
+    SENSITIVE_FILES.iter().any(|glob| matches_file_name(glob, path))
+}
+
+/// Sizes as people read them, e.g. "12.5 MB".
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+fn matches_file_name(glob: &str, path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    utils::glob_match(glob, name)
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "+/=_-".contains(c)
+}
+
+/// Random keys mix cases and digits; words, paths and identifiers rarely do.
+fn looks_random(token: &str) -> bool {
+    token.chars().any(|c| c.is_ascii_uppercase())
+        && token.chars().any(|c| c.is_ascii_lowercase())
+        && token.chars().any(|c| c.is_ascii_digit())
+}
+
+fn redact(matched: &str) -> String {
+    let shown: String = matched.chars().take(4).collect();
+    format!("{}… {} chars", shown, matched.chars().count())
+}
@@ -0,0 +1,132 @@
+use crate::branch;
+use crate::errors::Result;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// An entry of `git stash list`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StashEntry {
+    pub index: usize,
+    pub hash: String,
+    /// Branch the stash was made on; None for entries such as `autostash`.
+    pub branch: Option<String>,
+    pub message: String,
+    /// Relative creation date, e.g. "2 hours ago".
+    pub date: String,
+}
+
+impl StashEntry {
+    /// Parses a `git stash list --format=%gd%x1f%H%x1f%cr%x1f%gs` line.
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(4, '\x1f');
+        let index = fields.next()?.strip_prefix("stash@{")?.strip_suffix('}')?.parse().ok()?;
+        let hash = fields.next()?.to_string();
+        let date = fields.next()?.to_string();
+        let subject = fields.next().unwrap_or_default();
+
+        // "On main: message", or "WIP on main: 1a2b3c4 subject" for unnamed stashes
+        let (branch, message) = match subject.strip_prefix("On ").or_else(|| subject.strip_prefix("WIP on ")) {
+            Some(rest) => match rest.split_once(": ") {
+                Some((branch, message)) => (Some(branch.to_string()), message.to_string()),
+                None => (None, rest.to_string()),
+            },
+            None => (None, subject.to_string()),
+        };
+
+        Some(Self { index, hash, branch, message, date })
+    }
+
+    /// The `stash@{N}` name git commands accept.
+    pub fn reference(&self) -> String {
+        format!("stash@{{{}}}", self.index)
+    }
+}
+
+impl fmt::Display for StashEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.reference(), self.message)?;
+        match &self.branch {
+            Some(branch) => write!(f, " ({}, {})", branch, self.date),
+            None => write!(f, " ({})", self.date),
+        }
+    }
+}
+
+/// Finds the stash `query` refers to: an index (`2`), a `stash@{2}` name, or
+/// a fuzzy match on the stash messages, best match first.
+pub fn find_stashes<'a>(query: &str, stashes: &'a [StashEntry]) -> Vec<&'a StashEntry> {
+    let query = query.trim();
+    let index = query
+        .strip_prefix("stash@{")
+        .and_then(|rest| rest.strip_suffix('}'))
+        .unwrap_or(query)
+        .parse::<usize>();
+    if let Ok(index) = index {
+        return stashes.iter().filter(|s| s.index == index).collect();
+    }
+
+    let messages: Vec<String> = stashes.iter().map(|s| s.message.clone()).collect();
+    branch::fuzzy_filter(query, &messages)
+        .into_iter()
+        .filter_map(|message| stashes.iter().find(|s| &s.message == message))
+        .collect()
+}
+
+/// Stashes git-cli could not re-apply after a pull, recorded under
+/// `.git/git-cli/leftover-stashes` until they leave the stash list.
+pub struct LeftoverStashes {
+    path: PathBuf,
+}
+
+impl LeftoverStashes {
+    pub fn new(git_dir: &Path) -> Self {
+        Self {
+            path: git_dir.join("git-cli").join("leftover-stashes"),
+        }
+    }
+
+    pub fn add(&self, hash: &str) -> Result<()> {
+        let mut hashes = self.load()?;
+        if !hashes.iter().any(|h| h == hash) {
+            hashes.push(hash.to_string());
+        }
+        self.save(&hashes)
+    }
+
+    /// The recorded stashes that are still on the stash list. Those that were
+    /// applied or dropped in the meantime are forgotten.
+    pub fn remaining<'a>(&self, stashes: &'a [StashEntry]) -> Result<Vec<&'a StashEntry>> {
+        let hashes = self.load()?;
+        let remaining: Vec<&StashEntry> = stashes.iter().filter(|s| hashes.contains(&s.hash)).collect();
+
+        if remaining.len() != hashes.len() {
+            let kept: Vec<String> = remaining.iter().map(|s| s.hash.clone()).collect();
+            self.save(&kept)?;
+        }
+        Ok(remaining)
+    }
+
+    fn load(&self) -> Result<Vec<String>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(&self.path)?;
+        Ok(content.lines().map(str::trim).filter(|l| !l.is_empty()).map(str::to_string).collect())
+    }
+
+    fn save(&self, hashes: &[String]) -> Result<()> {
+        if hashes.is_empty() {
+            if self.path.exists() {
+                std::fs::remove_file(&self.path)?;
+            }
+            return Ok(());
+        }
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, hashes.join("\n") + "\n")?;
+        Ok(())
+    }
+}
@@ -1,10 +1,44 @@
 use crate::errors::{Result, GitCliError, ValidationError};
 use crate::emojis::{Emoji, EmojisObject};
-use crate::git::Change;
+use crate::git::{Change, CommitSignature, ConflictSide, ConflictState, FixupKind, SignatureStatus, StagedFile, Submodule};
+use crate::journal::JournalSession;
 use crate::config::Config;
+use crate::branch::StaleBranch;
+use crate::release::{Bump, ReleaseCommit};
+use crate::scan::Finding;
+use crate::hooks::FixerRun;
+use crate::stash::StashEntry;
+use crate::version_files::VersionFileState;
+use crate::workspace::RepoOutcome;
+use crate::validation::{ChangeGroup, ValidationService};
 use inquire::{Select, MultiSelect, Confirm, Text};
 use log::debug;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictAction {
+    Ours,
+    Theirs,
+    Edit,
+    MarkResolved,
+    Skip,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictNextStep {
+    Continue,
+    Resolve,
+    Abort,
+    Leave,
+}
+
+/// What to do with staged files that were not part of a partial selection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StagedExtrasAction {
+    Unstage,
+    CommitSelectionOnly,
+    CommitAll,
+}
+
 pub struct UIService {
     config: Config,
 }
@@ -24,6 +58,45 @@ impl UIService {
     }
 
     pub fn select_files_to_commit(&self, changes: &[Change]) -> Result<Vec<String>> {
+        self.select_changes("Select changes to add to the commit:", changes)
+    }
+
+    /// Lists what the commit will contain, marking files outside the selection.
+    pub fn show_staged_preview(&self, staged: &[StagedFile], extras: &[&StagedFile]) {
+        println!("\nTo be committed:");
+        for file in staged {
+            if extras.contains(&file) {
+                println!("  \x1b[1;33m{}\x1b[0m (staged before, not selected)", file);
+            } else {
+                println!("  \x1b[0;32m{}\x1b[0m", file);
+            }
+        }
+        println!();
+    }
+
+    pub fn select_staged_extras_action(&self, count: usize) -> Result<StagedExtrasAction> {
+        let unstage = format!("Unstage the {} files I did not select", count);
+        let selection_only = "Commit only my selection and keep the others staged".to_string();
+        let all = "Commit everything that is staged".to_string();
+
+        let selected = Select::new("The commit includes files you did not select:", vec![unstage.clone(), selection_only.clone(), all])
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        let action = match selected {
+            s if s == unstage => StagedExtrasAction::Unstage,
+            s if s == selection_only => StagedExtrasAction::CommitSelectionOnly,
+            _ => StagedExtrasAction::CommitAll,
+        };
+        debug!("User chose {:?} for files staged outside the selection", action);
+        Ok(action)
+    }
+
+    pub fn select_files_to_stash(&self, changes: &[Change]) -> Result<Vec<String>> {
+        self.select_changes("Select changes to stash:", changes)
+    }
+
+    fn select_changes(&self, prompt: &str, changes: &[Change]) -> Result<Vec<String>> {
         let options: Vec<String> = changes
             .iter()
             .map(|change| {
@@ -34,7 +107,7 @@ impl UIService {
             })
             .collect();
 
-        let selected = MultiSelect::new("Select changes to add to the commit:", options)
+        let selected = MultiSelect::new(prompt, options)
             .prompt()
             .map_err(GitCliError::InquireError)?;
 
@@ -54,13 +127,25 @@ impl UIService {
     }
 
     pub fn select_emoji(&self, emojis_object: &EmojisObject) -> Result<Emoji> {
+        self.select_emoji_with_default(emojis_object, None)
+    }
+
+    /// Same as `select_emoji`, but starts with the cursor on `default` when it
+    /// is part of the list.
+    pub fn select_emoji_with_default(&self, emojis_object: &EmojisObject, default: Option<&str>) -> Result<Emoji> {
         let options: Vec<String> = emojis_object
             .emojis
             .iter()
             .map(|emoji| format!("{} - {}", emoji.emoji, emoji.description))
             .collect();
 
+        let starting_cursor = default
+            .and_then(|default| crate::message::find_emoji(emojis_object, default))
+            .and_then(|emoji| emojis_object.emojis.iter().position(|e| e.emoji == emoji.emoji))
+            .unwrap_or(0);
+
         let selected = Select::new("Select an emoji for your commit message:", options)
+            .with_starting_cursor(starting_cursor)
             .prompt()
             .map_err(GitCliError::InquireError)?;
 
@@ -75,14 +160,23 @@ impl UIService {
     }
 
     pub fn get_commit_title(&self, changes: &[crate::git::Change]) -> Result<String> {
+        self.get_commit_title_with_initial(changes, None)
+    }
+
+    pub fn get_commit_title_with_initial(&self, changes: &[crate::git::Change], initial: Option<&str>) -> Result<String> {
         loop {
             let placeholder = self.generate_smart_placeholder(changes);
             
             let help_message = format!("Max length: {} characters", self.config.commit.max_title_length);
 
-            let title = Text::new("Enter commit title:")
+            let mut prompt = Text::new("Enter commit title:")
                 .with_placeholder(&placeholder)
-                .with_help_message(&help_message)
+                .with_help_message(&help_message);
+            if let Some(initial) = initial {
+                prompt = prompt.with_initial_value(initial);
+            }
+
+            let title = prompt
                 .prompt()
                 .map_err(GitCliError::InquireError)?;
 
@@ -109,8 +203,31 @@ impl UIService {
     }
 
     pub fn get_commit_message(&self) -> Result<Option<String>> {
-        let message = Text::new("Enter commit message (optional):")
-            .with_default("")
+        self.get_commit_message_with_initial(None)
+    }
+
+    pub fn get_commit_message_with_initial(&self, initial: Option<&str>) -> Result<Option<String>> {
+        // The text prompt is single-line, so multi-line bodies can only be kept or replaced
+        if let Some(initial) = initial.filter(|body| body.contains('\n')) {
+            println!("\nCurrent commit body:\n{}\n", initial);
+            let keep = Confirm::new("Keep the current commit body?")
+                .with_default(true)
+                .prompt()
+                .map_err(GitCliError::InquireError)?;
+
+            if keep {
+                self.validate_commit_body(initial)?;
+                return Ok(Some(initial.to_string()));
+            }
+        }
+
+        let mut prompt = Text::new("Enter commit message (optional):")
+            .with_default("");
+        if let Some(initial) = initial.filter(|body| !body.contains('\n')) {
+            prompt = prompt.with_initial_value(initial);
+        }
+
+        let message = prompt
             .prompt()
             .map_err(GitCliError::InquireError)?;
 
@@ -144,6 +261,596 @@ impl UIService {
         Ok(result)
     }
 
+    pub fn confirm_amend_pushed_commit(&self, hash: &str) -> Result<bool> {
+        self.show_warning(&format!(
+            "Commit {} has already been pushed. Amending it rewrites history and will require a force push.",
+            hash
+        ));
+
+        let result = Confirm::new("Do you still want to amend it?")
+            .with_default(false)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to amend pushed commit: {}", result);
+        Ok(result)
+    }
+
+    pub fn confirm_include_changes_in_amend(&self) -> Result<bool> {
+        let result = Confirm::new("Do you want to include the current changes in the amended commit?")
+            .with_default(false)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to include changes in amend: {}", result);
+        Ok(result)
+    }
+
+    /// Lets the user pick one of `commits` (`git log --oneline` lines) and
+    /// returns its abbreviated hash.
+    pub fn select_fixup_target(&self, commits: &[String]) -> Result<String> {
+        let selected = Select::new("Select the commit to fix up:", commits.to_vec())
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        let hash = selected
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        debug!("User selected fixup target: {}", hash);
+        Ok(hash)
+    }
+
+    pub fn select_fixup_kind(&self) -> Result<FixupKind> {
+        let options = vec![
+            "fixup! - fold changes in and keep the original message",
+            "squash! - fold changes in and edit the combined message",
+        ];
+
+        let selected = Select::new("How should the changes be folded in?", options)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        let kind = if selected.starts_with("squash!") { FixupKind::Squash } else { FixupKind::Fixup };
+        debug!("User selected fixup kind: {:?}", kind);
+        Ok(kind)
+    }
+
+    pub fn confirm_split_into_groups(&self, groups: &[ChangeGroup]) -> Result<bool> {
+        println!("\nProposed commits:");
+        for (index, group) in groups.iter().enumerate() {
+            println!(
+                "  {}. {} {} ({} files)",
+                index + 1,
+                group.emoji.unwrap_or("•"),
+                group.label,
+                group.changes.len()
+            );
+        }
+        println!();
+
+        let result = Confirm::new(&format!("Do you want to split the changes into {} commits?", groups.len()))
+            .with_default(false)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to split changes into groups: {}", result);
+        Ok(result)
+    }
+
+    pub fn confirm_commit_group(&self, group: &ChangeGroup) -> Result<bool> {
+        println!("\n{} {}:", group.emoji.unwrap_or("•"), group.label);
+        for change in &group.changes {
+            println!(
+                "  {}{}: {}\x1b[0m",
+                change.color, change.change_type, change.value
+            );
+        }
+
+        let result = Confirm::new("Do you want to commit this group?")
+            .with_default(true)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to commit group {}: {}", group.label, result);
+        Ok(result)
+    }
+
+    pub fn confirm_commit_remaining(&self, count: usize) -> Result<bool> {
+        let result = Confirm::new(&format!("Do you want to commit the {} remaining changes?", count))
+            .with_default(false)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to commit remaining changes: {}", result);
+        Ok(result)
+    }
+
+    pub fn show_journal_session(&self, session: &JournalSession) {
+        println!("\nLast session: git-cli {}", session.command);
+        for step in &session.steps {
+            let marker = if step.is_undoable() { "↺" } else { "✗" };
+            println!("  {} {}", marker, step.describe());
+        }
+        println!();
+    }
+
+    pub fn confirm_undo(&self, command: &str) -> Result<bool> {
+        let result = Confirm::new(&format!("Do you want to undo the last `{}` session?", command))
+            .with_default(false)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to undo: {}", result);
+        Ok(result)
+    }
+
+    pub fn show_conflicts(&self, state: ConflictState, files: &[String], current: Option<&str>, pending: &[String]) {
+        let operation = match state {
+            ConflictState::Rebase => "Rebase",
+            ConflictState::Merge => "Merge",
+            ConflictState::None => "Operation",
+        };
+
+        println!("\n{} stopped with conflicts in {} files:", operation, files.len());
+        for file in files {
+            println!("  \x1b[0;31mConflicted: {}\x1b[0m", file);
+        }
+
+        if let Some(current) = current {
+            println!("\nStopped at: {}", current);
+        }
+
+        if !pending.is_empty() {
+            println!("\n{} commits still pending:", pending.len());
+            for commit in pending {
+                println!("  {}", commit);
+            }
+        }
+        println!();
+    }
+
+    /// Asks how to resolve `file`. During a rebase "ours" is the upstream and
+    /// "theirs" is the local commit being replayed, so the labels spell that out.
+    pub fn select_conflict_action(&self, file: &str, state: ConflictState) -> Result<ConflictAction> {
+        let (ours, theirs) = match state {
+            ConflictState::Rebase => ("Keep upstream version (ours)", "Keep my version (theirs)"),
+            _ => ("Keep my version (ours)", "Keep their version (theirs)"),
+        };
+        let options = vec![ours, theirs, "Open in editor", "Mark as resolved", "Skip for now"];
+
+        let selected = Select::new(&format!("How do you want to resolve {}?", file), options)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        let action = match selected {
+            s if s == ours => ConflictAction::Ours,
+            s if s == theirs => ConflictAction::Theirs,
+            "Open in editor" => ConflictAction::Edit,
+            "Mark as resolved" => ConflictAction::MarkResolved,
+            _ => ConflictAction::Skip,
+        };
+
+        debug!("User chose {:?} for {}", action, file);
+        Ok(action)
+    }
+
+    /// Settles a file whose unstaged changes clash with a hook's rewrite.
+    /// Theirs is the unstaged version, ours the rewritten one.
+    pub fn select_unstaged_conflict_side(&self, file: &str) -> Result<ConflictSide> {
+        let keep_mine = "Keep my unstaged changes (the rewrite stays in the commit)";
+        let keep_rewrite = "Keep the rewritten file (my unstaged changes stay in the stash)";
+
+        let selected = Select::new(&format!("{} was rewritten by the hooks and has unstaged changes:", file), vec![keep_mine, keep_rewrite])
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        let side = if selected == keep_mine { ConflictSide::Theirs } else { ConflictSide::Ours };
+        debug!("User chose {:?} for {}", side, file);
+        Ok(side)
+    }
+
+    pub fn select_conflict_next_step(&self, can_continue: bool) -> Result<ConflictNextStep> {
+        let mut options = Vec::new();
+        if can_continue {
+            options.push("Continue");
+        } else {
+            options.push("Resolve remaining files");
+        }
+        options.push("Abort and restore the previous state");
+        options.push("Leave it for later");
+
+        let selected = Select::new("What do you want to do next?", options)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        let step = match selected {
+            "Continue" => ConflictNextStep::Continue,
+            "Resolve remaining files" => ConflictNextStep::Resolve,
+            "Abort and restore the previous state" => ConflictNextStep::Abort,
+            _ => ConflictNextStep::Leave,
+        };
+
+        debug!("User chose next step: {:?}", step);
+        Ok(step)
+    }
+
+    /// Plain force pushes discard remote work, so the user has to type the
+    /// branch name rather than just answer yes.
+    pub fn confirm_force_push(&self, remote: &str, branch: &str) -> Result<bool> {
+        self.show_warning(&format!(
+            "--force overwrites {}/{} regardless of what is there now. Prefer --force-with-lease.",
+            remote, branch
+        ));
+
+        let typed = Text::new(&format!("Type the branch name ({}) to confirm the force push:", branch))
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        let confirmed = typed.trim() == branch;
+        if !confirmed {
+            self.show_error("Branch name did not match; not force pushing.");
+        }
+
+        debug!("User confirmed force push: {}", confirmed);
+        Ok(confirmed)
+    }
+
+    pub fn confirm_set_upstream(&self, remote: &str, branch: &str) -> Result<bool> {
+        let result = Confirm::new(&format!("Set {}/{} as the upstream of {}?", remote, branch, branch))
+            .with_default(true)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to set upstream: {}", result);
+        Ok(result)
+    }
+
+    pub fn confirm_leave_protected_branch(&self, branch: &str) -> Result<bool> {
+        let result = Confirm::new(&format!("{} is protected. Move this work to a new branch?", branch))
+            .with_default(true)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to leave protected branch: {}", result);
+        Ok(result)
+    }
+
+    pub fn get_branch_name(&self, initial: &str) -> Result<String> {
+        let name = Text::new("New branch name:")
+            .with_initial_value(initial)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User entered branch name: {}", name);
+        Ok(name.trim().to_string())
+    }
+
+    /// Picks the type of work from the emoji list, returning the Conventional
+    /// Commits type of the chosen emoji.
+    pub fn select_branch_type(&self, emojis_object: &EmojisObject) -> Result<&'static str> {
+        let options: Vec<String> = emojis_object
+            .emojis
+            .iter()
+            .map(|emoji| format!("{} {} - {}", emoji.emoji, ValidationService::commit_type_for_emoji(&emoji.emoji), emoji.description))
+            .collect();
+
+        let selected = Select::new("Select the type of work for this branch:", options.clone())
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        let index = options.iter().position(|o| *o == selected).unwrap_or_default();
+        let commit_type = ValidationService::commit_type_for_emoji(&emojis_object.emojis[index].emoji);
+        debug!("User selected branch type: {}", commit_type);
+        Ok(commit_type)
+    }
+
+    pub fn get_branch_description(&self) -> Result<String> {
+        let description = Text::new("Describe the branch:")
+            .with_placeholder("add oauth login")
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User entered branch description: {}", description);
+        Ok(description)
+    }
+
+    pub fn get_issue_reference(&self) -> Result<Option<String>> {
+        let issue = Text::new("Issue reference (optional):")
+            .with_placeholder("123")
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        let issue = issue.trim().trim_start_matches('#').to_string();
+        debug!("User entered issue reference: {}", issue);
+        Ok(if issue.is_empty() { None } else { Some(issue) })
+    }
+
+    pub fn select_branch(&self, branches: &[&String]) -> Result<String> {
+        let options: Vec<String> = branches.iter().map(|b| b.to_string()).collect();
+        let selected = Select::new("Switch to branch:", options)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User selected branch: {}", selected);
+        Ok(selected)
+    }
+
+    pub fn confirm_stash_before_switch(&self, branch: &str) -> Result<bool> {
+        let result = Confirm::new(&format!("You have uncommitted changes. Stash them before switching to {}?", branch))
+            .with_default(true)
+            .with_help_message("Choose no to carry the changes over to the other branch")
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to stash before switching: {}", result);
+        Ok(result)
+    }
+
+    pub fn select_branches_to_delete(&self, branches: &[StaleBranch]) -> Result<Vec<StaleBranch>> {
+        let all: Vec<usize> = (0..branches.len()).collect();
+        let selected = MultiSelect::new("Select branches to delete:", branches.to_vec())
+            .with_default(&all)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User selected {} branches to delete", selected.len());
+        Ok(selected)
+    }
+
+    pub fn show_release_plan(&self, last_tag: Option<&str>, commits: &[ReleaseCommit], bump: Bump) {
+        match last_tag {
+            Some(tag) => println!("\n{} commits since {}:", commits.len(), tag),
+            None => println!("\n{} commits since the start of the history:", commits.len()),
+        }
+        for commit in commits {
+            let impact = match commit.bump {
+                Bump::None => "      ".to_string(),
+                bump => format!("{:<6}", bump.to_string()),
+            };
+            println!("  {} {} {}", impact, &commit.hash[..commit.hash.len().min(7)], commit.subject);
+        }
+        println!("\nRelease impact: {}\n", bump);
+    }
+
+    pub fn show_version_bumps(&self, bumps: &[VersionFileState], version: &str) {
+        if bumps.is_empty() {
+            return;
+        }
+
+        println!("\nVersion files:");
+        for file in bumps {
+            println!("  {}: {} → {}", file.path, file.version, version);
+        }
+    }
+
+    pub fn get_release_version(&self, proposed: &str) -> Result<String> {
+        let version = Text::new("Version to release:")
+            .with_initial_value(proposed)
+            .with_help_message("Proposed from the commits since the last release")
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User entered release version: {}", version);
+        Ok(version.trim().to_string())
+    }
+
+    pub fn confirm_create_tag(&self, tag: &str, notes: &str) -> Result<bool> {
+        println!("\n{}", notes);
+        let result = Confirm::new(&format!("Create tag {}?", tag))
+            .with_default(true)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to create tag: {}", result);
+        Ok(result)
+    }
+
+    pub fn confirm_push_tag(&self, tag: &str, remote: &str) -> Result<bool> {
+        let result = Confirm::new(&format!("Push {} to {}?", tag, remote))
+            .with_default(true)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to push tag: {}", result);
+        Ok(result)
+    }
+
+    pub fn show_signature(&self, signature: &CommitSignature) {
+        let signer = signature.signer.as_deref().map(|s| format!(" by {}", s)).unwrap_or_default();
+        let message = format!("Commit {}: {}{}", signature.hash, signature.status, signer);
+        match signature.status {
+            SignatureStatus::Good => self.show_success(&message),
+            SignatureStatus::GoodUntrusted | SignatureStatus::Unverifiable => self.show_info(&message),
+            SignatureStatus::Bad | SignatureStatus::Unsigned => self.show_warning(&message),
+        }
+    }
+
+    pub fn confirm_push_unsigned(&self) -> Result<bool> {
+        let result = Confirm::new("Push the unsigned commits anyway?")
+            .with_default(false)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to push unsigned commits: {}", result);
+        Ok(result)
+    }
+
+    pub fn show_submodules(&self, submodules: &[Submodule]) {
+        if submodules.is_empty() {
+            return;
+        }
+
+        println!("\nSubmodules:");
+        for submodule in submodules {
+            println!("  \x1b[1;36m{}\x1b[0m", submodule);
+            for commit in &submodule.new_commits {
+                println!("      {}", commit);
+            }
+        }
+        println!();
+    }
+
+    pub fn confirm_commit_in_submodule(&self, path: &str) -> Result<bool> {
+        let result = Confirm::new(&format!("Submodule {} has uncommitted changes. Commit them inside the submodule first?", path))
+            .with_default(true)
+            .with_help_message("Runs git-cli inside the submodule")
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to commit inside submodule {}: {}", path, result);
+        Ok(result)
+    }
+
+    pub fn confirm_submodule_commit(&self, path: &str, title: &str, body: Option<&str>) -> Result<bool> {
+        println!("\n{}", title);
+        if let Some(body) = body {
+            println!("\n{}", body);
+        }
+        let result = Confirm::new(&format!("Commit the updated {} pointer with this message?", path))
+            .with_default(true)
+            .with_help_message("Choose no to leave the pointer unstaged")
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to commit submodule pointer {}: {}", path, result);
+        Ok(result)
+    }
+
+    pub fn confirm_stash_all_files(&self) -> Result<bool> {
+        let result = Confirm::new("Do you want to stash all changes?")
+            .with_default(true)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to stash all files: {}", result);
+        Ok(result)
+    }
+
+    pub fn get_stash_message(&self, initial: &str) -> Result<String> {
+        let message = Text::new("Stash name:")
+            .with_initial_value(initial)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User entered stash name: {}", message);
+        Ok(message.trim().to_string())
+    }
+
+    pub fn show_stashes(&self, stashes: &[StashEntry], leftovers: &[&StashEntry]) {
+        for entry in stashes {
+            if leftovers.iter().any(|l| l.hash == entry.hash) {
+                println!("  {} \x1b[0;33m(left behind by a failed pull)\x1b[0m", entry);
+            } else {
+                println!("  {}", entry);
+            }
+        }
+    }
+
+    pub fn select_stash(&self, stashes: &[&StashEntry], action: &str) -> Result<StashEntry> {
+        let options: Vec<String> = stashes.iter().map(|s| s.to_string()).collect();
+        let selected = Select::new(&format!("Stash to {}:", action), options)
+            .raw_prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User selected stash: {}", selected.value);
+        Ok(stashes[selected.index].clone())
+    }
+
+    pub fn show_stash_diff(&self, diff: &str) {
+        println!("{}", diff.trim_end());
+        println!();
+    }
+
+    /// Lists the files the pre-commit hooks rewrote, with the diff of what changed.
+    pub fn show_hook_changes(&self, files: &[String], diff: &str) {
+        println!("\nFiles rewritten by the pre-commit hooks:");
+        for file in files {
+            println!("  \x1b[1;33m{}\x1b[0m", file);
+        }
+        if !diff.trim().is_empty() {
+            println!("\n{}", diff.trim_end());
+        }
+        println!();
+    }
+
+    /// Summarizes the fixers that ran, followed by the diff of what they changed.
+    pub fn show_fixer_summary(&self, runs: &[FixerRun], diff: &str) {
+        println!("\nFixers:");
+        for run in runs {
+            if run.changed.is_empty() {
+                println!("  {} ({} files, unchanged)", run.command, run.files);
+            } else {
+                println!("  \x1b[1;33m{}\x1b[0m ({} files, {} changed)", run.command, run.files, run.changed.len());
+                for file in &run.changed {
+                    println!("      {}", file);
+                }
+            }
+        }
+        if !diff.trim().is_empty() {
+            println!("\n{}", diff.trim_end());
+        }
+        println!();
+    }
+
+    pub fn show_scan_findings(&self, findings: &[Finding]) {
+        println!("\nThe staged changes look like they contain secrets or oversized files:");
+        for finding in findings {
+            println!("  \x1b[0;31m{}\x1b[0m", finding);
+        }
+        println!();
+    }
+
+    pub fn confirm_lfs_track(&self, path: &str, size: &str) -> Result<bool> {
+        let result = Confirm::new(&format!("{} is {}. Move it to Git LFS?", path, size))
+            .with_default(true)
+            .with_help_message("Runs `git lfs track` and stages an LFS pointer instead")
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to track {} with Git LFS: {}", path, result);
+        Ok(result)
+    }
+
+    pub fn confirm_commit_with_findings(&self, count: usize) -> Result<bool> {
+        let result = Confirm::new(&format!("Commit anyway, despite {} findings?", count))
+            .with_default(false)
+            .with_help_message("Add false positives to the allowlist file to stop seeing them")
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to commit despite scanner findings: {}", result);
+        Ok(result)
+    }
+
+    pub fn confirm_drop_stash(&self, stash: &StashEntry) -> Result<bool> {
+        let result = Confirm::new(&format!("Drop {}?", stash))
+            .with_default(false)
+            .prompt()
+            .map_err(GitCliError::InquireError)?;
+
+        debug!("User chose to drop stash: {}", result);
+        Ok(result)
+    }
+
+    pub fn show_workspace_report(&self, report: &[(String, RepoOutcome)]) {
+        println!("\nWorkspace summary:");
+        for (name, outcome) in report {
+            match outcome {
+                RepoOutcome::Skipped(_) => println!("  - {}: {}", name, outcome),
+                RepoOutcome::Committed { .. } | RepoOutcome::Pushed { .. } => {
+                    println!("  \x1b[0;32m✔ {}: {}\x1b[0m", name, outcome)
+                }
+                RepoOutcome::Failed(_) | RepoOutcome::PushFailed { .. } => {
+                    println!("  \x1b[0;31m✘ {}: {}\x1b[0m", name, outcome)
+                }
+            }
+        }
+        println!();
+    }
+
     pub fn show_changes(&self, changes: &[Change]) {
         if changes.is_empty() {
             println!("No changes found.");
@@ -196,7 +903,7 @@ impl UIService {
         }
     }
 
-    fn validate_commit_title(&self, title: &str) -> Result<()> {
+    pub fn validate_commit_title(&self, title: &str) -> Result<()> {
         let mut errors = Vec::new();
 
         // Check length
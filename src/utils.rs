@@ -1,4 +1,7 @@
 use log::info;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
 
 pub fn format_error_message(message: &str) -> String {
     format!("\x1b[0;31m{}\x1b[0m", message)
@@ -32,6 +35,33 @@ pub fn init_logger(debug: bool) {
     }
 }
 
+/// Matches `text` against a shell-style glob where `*` matches any run of
+/// characters (including `/`) and `?` matches exactly one.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 pub fn truncate_string(s: &str, max_length: usize) -> String {
     if s.len() <= max_length {
         s.to_string()
@@ -39,3 +69,26 @@ pub fn truncate_string(s: &str, max_length: usize) -> String {
         format!("{}...", &s[..max_length.saturating_sub(3)])
     }
 }
+
+/// Replaces the default SIGINT behaviour (exiting mid-way) with a flag that
+/// the workflow checks between steps; a second Ctrl-C exits right away. Must
+/// be called within the tokio runtime.
+pub fn spawn_interrupt_listener() {
+    tokio::spawn(async {
+        loop {
+            if tokio::signal::ctrl_c().await.is_err() {
+                return;
+            }
+            // Printed directly, since the log level may hide warnings
+            if INTERRUPTED.swap(true, Ordering::SeqCst) {
+                eprintln!("\n{}", format_error_message("Interrupted again, exiting"));
+                std::process::exit(130);
+            }
+            eprintln!("\n{}", format_warning_message("Interrupted, cancelling after the current step (press Ctrl-C again to exit now)..."));
+        }
+    });
+}
+
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
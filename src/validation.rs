@@ -2,33 +2,116 @@ use crate::git::Change;
 
 pub struct ValidationService;
 
+#[derive(Debug, Clone)]
+pub struct ChangeGroup {
+    pub label: String,
+    pub emoji: Option<&'static str>,
+    pub changes: Vec<Change>,
+}
+
+impl ChangeGroup {
+    pub fn files(&self) -> Vec<String> {
+        self.changes.iter().map(|c| c.value.clone()).collect()
+    }
+}
+
 impl ValidationService {
     pub fn suggest_emoji_by_files(changed_files: &[String]) -> Vec<&'static str> {
-        let mut suggestions = Vec::new();
-        
-        for file in changed_files {
-            let file_lower = file.to_lowercase();
-            
-            if file_lower.ends_with(".md") || file_lower.ends_with(".rst") || file_lower.ends_with(".txt") {
-                suggestions.push("📝"); // docs
-            } else if file_lower.contains("test") || file_lower.contains("spec") {
-                suggestions.push("✅"); // test
-            } else if file_lower.ends_with(".css") || file_lower.ends_with(".scss") || file_lower.ends_with(".sass") {
-                suggestions.push("💄"); // style
-            } else if file_lower.ends_with(".json") || file_lower.ends_with(".toml") || file_lower.ends_with(".yaml") || file_lower.ends_with(".yml") {
-                suggestions.push("🔧"); // config
-            } else if file_lower.starts_with("dockerfile") || file_lower.ends_with(".dockerfile") {
-                suggestions.push("🐳"); // docker
-            } else if file_lower.contains("security") || file_lower.contains("auth") {
-                suggestions.push("🔒"); // security
-            } else if file_lower.contains("performance") || file_lower.contains("perf") {
-                suggestions.push("⚡"); // performance
-            }
-        }
+        let mut suggestions: Vec<&'static str> = changed_files
+            .iter()
+            .filter_map(|file| Self::suggest_emoji_for_file(file))
+            .collect();
         
         // Remove duplicates and return
         suggestions.sort();
         suggestions.dedup();
         suggestions
     }
+
+    pub fn suggest_emoji_for_file(file: &str) -> Option<&'static str> {
+        let file_lower = file.to_lowercase();
+        
+        if file_lower.ends_with(".md") || file_lower.ends_with(".rst") || file_lower.ends_with(".txt") {
+            Some("📝") // docs
+        } else if file_lower.contains("test") || file_lower.contains("spec") {
+            Some("✅") // test
+        } else if file_lower.ends_with(".css") || file_lower.ends_with(".scss") || file_lower.ends_with(".sass") {
+            Some("💄") // style
+        } else if file_lower.ends_with(".json") || file_lower.ends_with(".toml") || file_lower.ends_with(".yaml") || file_lower.ends_with(".yml") {
+            Some("🔧") // config
+        } else if file_lower.starts_with("dockerfile") || file_lower.ends_with(".dockerfile") {
+            Some("🐳") // docker
+        } else if file_lower.contains("security") || file_lower.contains("auth") {
+            Some("🔒") // security
+        } else if file_lower.contains("performance") || file_lower.contains("perf") {
+            Some("⚡") // performance
+        } else {
+            None
+        }
+    }
+
+    /// Proposes one commit per cluster of related changes: files with a
+    /// suggested emoji are grouped by that category, the rest by their
+    /// top-level scope (the first two path components).
+    pub fn group_changes(changes: &[Change]) -> Vec<ChangeGroup> {
+        let mut groups: Vec<ChangeGroup> = Vec::new();
+
+        for change in changes {
+            let emoji = Self::suggest_emoji_for_file(&change.value);
+            let label = match emoji {
+                Some(emoji) => Self::category_label(emoji).to_string(),
+                None => Self::scope_of(&change.value),
+            };
+
+            match groups.iter_mut().find(|g| g.label == label) {
+                Some(group) => group.changes.push(change.clone()),
+                None => groups.push(ChangeGroup {
+                    label,
+                    emoji,
+                    changes: vec![change.clone()],
+                }),
+            }
+        }
+
+        groups
+    }
+
+    fn category_label(emoji: &str) -> &'static str {
+        match emoji {
+            "📝" => "docs",
+            "✅" => "tests",
+            "💄" => "style",
+            "🔧" => "config",
+            "🐳" => "docker",
+            "🔒" => "security",
+            "⚡" => "performance",
+            _ => "other",
+        }
+    }
+
+    fn scope_of(file: &str) -> String {
+        let components: Vec<&str> = file.trim_end_matches('/').split('/').collect();
+        if components.len() <= 1 {
+            "root".to_string()
+        } else {
+            components[..components.len().min(3) - 1].join("/")
+        }
+    }
+
+    /// Maps a gitmoji to the Conventional Commits type it stands for.
+    pub fn commit_type_for_emoji(emoji: &str) -> &'static str {
+        match emoji.trim_end_matches('\u{fe0f}') {
+            "✨" | "🎉" => "feat",
+            "🐛" | "🚑" | "🩹" | "🔒" | "🥅" => "fix",
+            "📝" | "💡" | "📄" => "docs",
+            "🎨" | "💄" | "🚨" => "style",
+            "♻" | "🚚" | "🏗" => "refactor",
+            "⚡" => "perf",
+            "✅" | "🧪" | "📸" => "test",
+            "👷" | "💚" => "ci",
+            "📦" | "➕" | "➖" | "⬆" | "⬇" | "📌" | "🔨" => "build",
+            "⏪" => "revert",
+            _ => "chore",
+        }
+    }
 }
@@ -0,0 +1,181 @@
+use crate::config::VersionFile;
+use crate::errors::{GitCliError, Result};
+use regex::Regex;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A configured version file and the version it currently holds.
+#[derive(Debug, Clone)]
+pub struct VersionFileState {
+    /// The path as configured, relative to the work tree root.
+    pub path: String,
+    pub location: PathBuf,
+    pub format: VersionFormat,
+    pub version: String,
+}
+
+/// How the version is stored in a file, inferred from its name unless a
+/// `pattern` is configured.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VersionFormat {
+    Cargo,
+    Npm,
+    Pyproject,
+    Pattern(String),
+}
+
+impl VersionFormat {
+    pub fn for_file(file: &VersionFile) -> Result<Self> {
+        if let Some(pattern) = &file.pattern {
+            return Ok(VersionFormat::Pattern(pattern.clone()));
+        }
+
+        match Path::new(&file.path).file_name().and_then(|name| name.to_str()) {
+            Some("Cargo.toml") => Ok(VersionFormat::Cargo),
+            Some("package.json") => Ok(VersionFormat::Npm),
+            Some("pyproject.toml") => Ok(VersionFormat::Pyproject),
+            _ => Err(GitCliError::ConfigError(format!(
+                "Cannot tell where the version is stored in {}; add a `pattern` for it",
+                file.path
+            ))),
+        }
+    }
+
+    /// Byte range of the version string inside `content`.
+    pub fn locate(&self, content: &str) -> Result<Range<usize>> {
+        let range = match self {
+            VersionFormat::Cargo => toml_version(content, &["package", "workspace.package"]),
+            VersionFormat::Pyproject => toml_version(content, &["project", "tool.poetry"]),
+            VersionFormat::Npm => npm_version(content)?,
+            VersionFormat::Pattern(pattern) => {
+                let regex = Regex::new(pattern)
+                    .map_err(|e| GitCliError::ConfigError(format!("Invalid version pattern {}: {}", pattern, e)))?;
+                regex
+                    .captures(content)
+                    .and_then(|captures| captures.name("version").or_else(|| captures.get(1)))
+                    .map(|m| m.range())
+            }
+        };
+
+        range.ok_or_else(|| GitCliError::ValidationError("No version found".to_string()))
+    }
+
+    pub fn read(&self, content: &str) -> Result<String> {
+        Ok(content[self.locate(content)?].to_string())
+    }
+
+    /// `content` with the version replaced, leaving all other formatting alone.
+    pub fn replace(&self, content: &str, version: &str) -> Result<String> {
+        let range = self.locate(content)?;
+        Ok(format!("{}{}{}", &content[..range.start], version, &content[range.end..]))
+    }
+}
+
+/// Finds `version = "..."` directly under one of the given TOML tables.
+fn toml_version(content: &str, tables: &[&str]) -> Option<Range<usize>> {
+    let line_regex = Regex::new(r#"^\s*version\s*=\s*["']([^"']*)["']"#).ok()?;
+    let mut in_table = false;
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            let name = trimmed.trim_start_matches('[').split(']').next().unwrap_or_default().trim();
+            in_table = tables.contains(&name);
+        } else if in_table {
+            if let Some(m) = line_regex.captures(line).and_then(|captures| captures.get(1)) {
+                return Some(offset + m.start()..offset + m.end());
+            }
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Finds the top-level `"version"` of a package.json rather than a nested
+/// one, such as the version of a dependency.
+fn npm_version(content: &str) -> Result<Option<Range<usize>>> {
+    let value_regex = Regex::new(r#"^\s*:\s*"([^"]*)""#).expect("built-in version pattern");
+    let bytes = content.as_bytes();
+    let mut depth = 0;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth -= 1,
+            b'"' => {
+                let start = index + 1;
+                index = start;
+                while index < bytes.len() && bytes[index] != b'"' {
+                    index += if bytes[index] == b'\\' { 2 } else { 1 };
+                }
+                if index >= bytes.len() {
+                    return Err(GitCliError::ValidationError("Unterminated string in package.json".to_string()));
+                }
+                if depth == 1 && &content[start..index] == "version" {
+                    if let Some(m) = value_regex.captures(&content[index + 1..]).and_then(|c| c.get(1)) {
+                        return Ok(Some(index + 1 + m.start()..index + 1 + m.end()));
+                    }
+                }
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    Ok(None)
+}
+
+/// The lockfile `refresh_lockfile` would regenerate for `manifest`, when
+/// there is one.
+pub fn lockfile_for(format: &VersionFormat, manifest: &Path) -> Option<PathBuf> {
+    let dir = manifest.parent().unwrap_or(Path::new("."));
+    let lockfile = match format {
+        VersionFormat::Cargo => "Cargo.lock",
+        VersionFormat::Npm => "package-lock.json",
+        VersionFormat::Pyproject if dir.join("uv.lock").exists() => "uv.lock",
+        VersionFormat::Pyproject => "poetry.lock",
+        VersionFormat::Pattern(_) => return None,
+    };
+
+    // Cargo workspaces keep a single lockfile next to the root manifest
+    match format {
+        VersionFormat::Cargo => dir.ancestors().map(|d| d.join(lockfile)).find(|path| path.exists()),
+        _ => Some(dir.join(lockfile)).filter(|path| path.exists()),
+    }
+}
+
+/// Regenerates the lockfile that goes with `manifest` after its version
+/// changed, returning the lockfile when it exists and was refreshed.
+pub fn refresh_lockfile(format: &VersionFormat, manifest: &Path) -> Result<Option<PathBuf>> {
+    let Some(lockfile) = lockfile_for(format, manifest) else {
+        return Ok(None);
+    };
+    let dir = manifest.parent().unwrap_or(Path::new("."));
+    let (program, args): (&str, &[&str]) = match format {
+        VersionFormat::Cargo => ("cargo", &["update", "--workspace", "--offline"]),
+        VersionFormat::Npm => ("npm", &["install", "--package-lock-only", "--ignore-scripts"]),
+        VersionFormat::Pyproject if lockfile.ends_with("uv.lock") => ("uv", &["lock"]),
+        VersionFormat::Pyproject => ("poetry", &["lock", "--no-update"]),
+        VersionFormat::Pattern(_) => return Ok(None),
+    };
+
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| GitCliError::GitCommandFailed(format!("Failed to run {}: {}", program, e)))?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(GitCliError::GitCommandFailed(format!(
+            "`{} {}` failed: {}",
+            program,
+            args.join(" "),
+            error_msg.trim()
+        )));
+    }
+
+    Ok(Some(lockfile))
+}
@@ -0,0 +1,72 @@
+use crate::errors::{GitCliError, Result};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Workspace file looked up in the current directory when none is given.
+pub const DEFAULT_FILE: &str = ".git-cli-workspace";
+
+/// A repository listed in a workspace file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspaceRepo {
+    /// The path as written in the workspace file.
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Parses a workspace file: one repository path per line, relative to the
+/// file's directory. Blank lines and `#` comments are ignored.
+pub fn parse_workspace(content: &str, base_dir: &Path) -> Vec<WorkspaceRepo> {
+    let mut repos: Vec<WorkspaceRepo> = Vec::new();
+    for line in content.lines() {
+        let line = line.split_once('#').map_or(line, |(path, _)| path).trim();
+        if line.is_empty() || repos.iter().any(|r| r.name == line) {
+            continue;
+        }
+        repos.push(WorkspaceRepo {
+            name: line.to_string(),
+            path: base_dir.join(line),
+        });
+    }
+    repos
+}
+
+pub fn load_workspace(path: &Path) -> Result<Vec<WorkspaceRepo>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| GitCliError::ConfigError(format!("Cannot read workspace file {}: {}", path.display(), e)))?;
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+
+    let repos = parse_workspace(&content, base_dir);
+    if repos.is_empty() {
+        return Err(GitCliError::ConfigError(format!("Workspace file {} lists no repositories", path.display())));
+    }
+    Ok(repos)
+}
+
+/// What happened in one repository of a workspace commit.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepoOutcome {
+    Skipped(String),
+    Failed(String),
+    Committed { hash: String },
+    Pushed { hash: String, target: String },
+    /// The commit was made, but pushing it failed.
+    PushFailed { hash: String, error: String },
+}
+
+impl RepoOutcome {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, RepoOutcome::Failed(_) | RepoOutcome::PushFailed { .. })
+    }
+}
+
+impl fmt::Display for RepoOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RepoOutcome::Skipped(reason) => write!(f, "skipped ({})", reason),
+            RepoOutcome::Failed(error) => write!(f, "FAILED: {}", error),
+            RepoOutcome::Committed { hash } => write!(f, "committed {}", hash),
+            RepoOutcome::Pushed { hash, target } => write!(f, "committed {} and pushed to {}", hash, target),
+            RepoOutcome::PushFailed { hash, error } => write!(f, "committed {}, PUSH FAILED: {}", hash, error),
+        }
+    }
+}